@@ -6,6 +6,7 @@ use equations::Align;
 use lists::List;
 use paragraph::Paragraph;
 use section::Section;
+use tables::Table;
 
 /// The root Document node.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -41,10 +42,27 @@ impl Document {
     }
 
     /// Iterate over the Elements in this document.
-    pub fn iter(&self) -> Iter<Element> {
+    pub fn iter(&self) -> Iter<'_, Element> {
         self.elements.iter()
     }
 
+    /// Add a table to the `Document`, loading any packages it needs.
+    ///
+    /// The table is rendered immediately, so later changes to `table` won't
+    /// show up in the document.
+    pub fn add_table(&mut self, table: &Table) -> &mut Self {
+        table.prepare_document(self);
+        self.push(Element::UserDefined(table.render()))
+    }
+
+    /// Add some LaTeX to the `Document` as-is, without escaping it.
+    ///
+    /// This is handy for wrapping other elements (e.g. a table added with
+    /// `add_table()`) in environments which aren't supported directly.
+    pub fn add_raw(&mut self, latex: &str) -> &mut Self {
+        self.push(Element::UserDefined(latex.to_string()))
+    }
+
     /// A convience method to include one document into
     /// another by cloning the individual nodes.
     pub fn push_doc(&mut self, doc: &Document) -> &mut Self {
@@ -160,9 +178,10 @@ where
 }
 
 /// The kind of Document being generated.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 #[allow(missing_docs)]
 pub enum DocumentClass {
+    #[default]
     Article,
     Book,
     Report,
@@ -172,12 +191,6 @@ pub enum DocumentClass {
     Other(String),
 }
 
-impl Default for DocumentClass {
-    fn default() -> Self {
-        DocumentClass::Article
-    }
-}
-
 impl Display for DocumentClass {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match *self {
@@ -242,14 +255,58 @@ impl Preamble {
     }
 
     /// Add a package import to the preamble.
+    ///
+    /// Packages which have already been imported are skipped, so this can
+    /// safely be called once for everything which needs the package.
     pub fn use_package(&mut self, name: &str) -> &mut Self {
+        if !self.uses_package(name) {
+            self.contents.push(PreambleElement::UsePackage {
+                package: name.to_string(),
+                argument: None,
+            });
+        }
+        self
+    }
+
+    /// Import a package with an option (e.g. `xcolor` with `table`).
+    ///
+    /// If the package has already been imported the option is added to its
+    /// existing import instead, so the package is still only loaded once.
+    pub fn use_package_with_option(&mut self, name: &str, option: &str) -> &mut Self {
+        for element in &mut self.contents {
+            if let PreambleElement::UsePackage { ref package, ref mut argument } = *element {
+                if package != name {
+                    continue;
+                }
+
+                let has_option = argument
+                    .as_ref()
+                    .is_some_and(|arg| arg.split(',').any(|o| o.trim() == option));
+                if !has_option {
+                    *argument = Some(match argument.take() {
+                        Some(ref arg) if !arg.is_empty() => format!("{},{}", arg, option),
+                        _ => option.to_string(),
+                    });
+                }
+                return self;
+            }
+        }
+
         self.contents.push(PreambleElement::UsePackage {
             package: name.to_string(),
-            argument: None,
+            argument: Some(option.to_string()),
         });
         self
     }
 
+    /// Has this package already been imported (with or without an argument)?
+    pub fn uses_package(&self, name: &str) -> bool {
+        self.contents.iter().any(|element| match *element {
+            PreambleElement::UsePackage { ref package, .. } => package == name,
+            _ => false,
+        })
+    }
+
     /// Interface of most commonly used way to write a `/newcommand` line in latex.  
     /// If you want to create `/newcommand` in 
     /// other ways(like add default argument or do not assign the num of arguments), 
@@ -272,7 +329,7 @@ impl Preamble {
     }
 
     /// Iterate over each package used in the Preamble.
-    pub fn iter(&self) -> Iter<PreambleElement> {
+    pub fn iter(&self) -> Iter<'_, PreambleElement> {
         self.contents.iter()
     }
 
@@ -1,4 +1,3 @@
-use std::ops::Deref;
 use std::slice::Iter;
 
 /// A single equation.
@@ -80,7 +79,7 @@ impl Equation {
 
     /// Get the equation label, if there is one.
     pub fn get_label(&self) -> Option<&str> {
-        self.label.as_ref().map(Deref::deref)
+        self.label.as_deref()
     }
 
     /// Is this equation numbered?
@@ -129,7 +128,7 @@ impl Align {
     }
 
     /// Iterate over each of this equations in the list.
-    pub fn iter(&self) -> Iter<Equation> {
+    pub fn iter(&self) -> Iter<'_, Equation> {
         self.items.iter()
     }
 
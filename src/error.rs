@@ -0,0 +1,74 @@
+//! The crate's general-purpose error type.
+
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+use failure::Fail;
+
+use tables::TableError;
+
+/// Everything which can go wrong when building and writing out a document,
+/// so different steps can be combined with `?`.
+///
+/// Like the rest of the crate's errors this is a `failure::Fail`, so it can
+/// also be turned into a `failure::Error`.
+#[derive(Debug)]
+pub enum Error {
+    /// A table couldn't be built.
+    Table(TableError),
+    /// Writing the output failed.
+    Io(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Error::Table(ref e) => write!(f, "Invalid table: {}", e),
+            Error::Io(ref e) => write!(f, "Unable to write the output: {}", e),
+        }
+    }
+}
+
+impl Fail for Error {
+    fn cause(&self) -> Option<&dyn Fail> {
+        match *self {
+            Error::Table(ref e) => Some(e),
+            Error::Io(ref e) => Some(e),
+        }
+    }
+}
+
+impl From<TableError> for Error {
+    fn from(e: TableError) -> Self {
+        Error::Table(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_table_errors() {
+        let err = Error::from(TableError::CellContainsNewline);
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid table: Cells can't contain newlines"
+        );
+        assert!(err.cause().is_some());
+    }
+
+    #[test]
+    fn convert_into_a_failure_error() {
+        let err: failure::Error = Error::from(TableError::CellContainsNewline).into();
+
+        assert!(err.downcast_ref::<Error>().is_some());
+    }
+}
@@ -108,15 +108,23 @@ extern crate failure;
 
 mod document;
 mod equations;
+mod error;
 mod lists;
 mod paragraph;
 mod section;
+mod tables;
 mod visitor;
 
 pub use document::{Document, DocumentClass, Element, Preamble, PreambleElement};
 pub use equations::{Align, Equation};
+pub use error::Error;
 pub use lists::{Item, List, ListKind};
 pub use paragraph::{Paragraph, ParagraphElement};
 pub use section::Section;
+pub use tables::{
+    BorderStyle, CaptionPosition, Cell, CellStyle, Column, Content, Row, Rule, RuleWeight, Segment,
+    Symbol, Table, TableBuilder, TableError, TableKind, TableWidth, TextRenderOptions, Trim,
+    VAlign,
+};
 
 pub use visitor::{print, Visitor};
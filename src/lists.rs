@@ -80,7 +80,7 @@ impl List {
     }
 
     /// Iterate over the items in the list.
-    pub fn iter(&self) -> Iter<Item> {
+    pub fn iter(&self) -> Iter<'_, Item> {
         self.items.iter()
     }
 }
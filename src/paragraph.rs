@@ -52,7 +52,7 @@ impl Paragraph {
     }
 
     /// Iterate over the `ParagraphElement`s in this `Paragraph`.
-    pub fn iter(&self) -> Iter<ParagraphElement> {
+    pub fn iter(&self) -> Iter<'_, ParagraphElement> {
         self.elements.iter()
     }
 }
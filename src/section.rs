@@ -33,7 +33,7 @@ impl Section {
     }
 
     /// Iterate over the elements in this list.
-    pub fn iter(&self) -> Iter<Element> {
+    pub fn iter(&self) -> Iter<'_, Element> {
         self.elements.iter()
     }
 
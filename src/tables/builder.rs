@@ -0,0 +1,174 @@
+use super::{Table, TableError, TableKind};
+
+/// A builder for creating a `Table` in a single expression.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::{Table, TableKind};
+///
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let table = Table::builder()
+///     .kind(TableKind::Tabularx)
+///     .columns("lX")
+///     .caption("Planets")
+///     .booktabs(true)
+///     .row(vec!["Mercury", "The smallest planet"])
+///     .row(vec!["Jupiter", "The largest planet"])
+///     .build()?;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// # run().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableBuilder {
+    kind: TableKind,
+    width: String,
+    columns: String,
+    caption: Option<String>,
+    label: Option<String>,
+    booktabs: bool,
+    rows: Vec<Vec<String>>,
+}
+
+impl TableBuilder {
+    /// Create a builder for a `Tabular` table spanning `\textwidth`.
+    pub fn new() -> TableBuilder {
+        TableBuilder {
+            kind: TableKind::Tabular,
+            width: "textwidth".to_string(),
+            columns: String::new(),
+            caption: None,
+            label: None,
+            booktabs: false,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Set the environment used to typeset the table.
+    pub fn kind(mut self, kind: TableKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Set the table's width, without the leading backslash (e.g.
+    /// `linewidth`).
+    pub fn width(mut self, width: &str) -> Self {
+        self.width = width.to_string();
+        self
+    }
+
+    /// Set the column specification (e.g. `lcr`).
+    pub fn columns(mut self, column_types: &str) -> Self {
+        self.columns = column_types.to_string();
+        self
+    }
+
+    /// Give the table a caption.
+    pub fn caption(mut self, text: &str) -> Self {
+        self.caption = Some(text.to_string());
+        self
+    }
+
+    /// Give the table a label.
+    pub fn label(mut self, id: &str) -> Self {
+        self.label = Some(id.to_string());
+        self
+    }
+
+    /// Set whether the table should be drawn with `booktabs` rules.
+    pub fn booktabs(mut self, enabled: bool) -> Self {
+        self.booktabs = enabled;
+        self
+    }
+
+    /// Add a row made up of plain cells with the provided values.
+    pub fn row<I, S>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(values.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Create the `Table`, making sure every row has the correct number of
+    /// columns.
+    pub fn build(self) -> Result<Table, TableError> {
+        let mut table = Table::new(self.kind, &self.width, &self.columns);
+        table.use_booktabs(self.booktabs);
+
+        if let Some(ref caption) = self.caption {
+            table.caption(caption);
+        }
+        if let Some(ref label) = self.label {
+            table.label(label);
+        }
+
+        for (i, row) in self.rows.into_iter().enumerate() {
+            table.push_row_values(row).map_err(|e| e.in_row(i))?;
+        }
+
+        Ok(table)
+    }
+}
+
+impl Default for TableBuilder {
+    fn default() -> Self {
+        TableBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_a_full_table() {
+        let should_be = r"\begin{table}
+\begin{tabularx}{\linewidth}{lX}
+\toprule
+Mercury & The smallest planet\\
+Jupiter & The largest planet\\
+\bottomrule
+\end{tabularx}
+\caption{Planets}
+\label{tab:planets}
+\end{table}";
+
+        let table = Table::builder()
+            .kind(TableKind::Tabularx)
+            .width("linewidth")
+            .columns("lX")
+            .caption("Planets")
+            .label("tab:planets")
+            .booktabs(true)
+            .row(vec!["Mercury", "The smallest planet"])
+            .row(vec!["Jupiter", "The largest planet"])
+            .build()
+            .unwrap();
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn build_checks_every_row() {
+        let got = Table::builder()
+            .columns("ll")
+            .row(vec!["a", "b"])
+            .row(vec!["c"])
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::WrongNumberOfColumnsInRow {
+                row_index: 1,
+                provided: 1,
+                required: 2,
+            }
+        );
+    }
+}
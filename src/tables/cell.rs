@@ -0,0 +1,954 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{Table, TableError};
+
+/// A single cell in a table `Row`.
+///
+/// The cell's value should be a paragraph without newlines, so prefer
+/// creating cells with `Cell::new()` which checks this for you. Any characters
+/// which are special to LaTeX (e.g. `%` or `&`) are escaped when the cell is
+/// rendered, unless the cell is marked as `raw`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Cell {
+    /// The cell's contents, as plain text.
+    ///
+    /// This is what the cell shows when exported to something other than
+    /// LaTeX (e.g. HTML) and what it is sorted by, while `content` decides
+    /// how it is written into a LaTeX table. Prefer `set_value()` when
+    /// changing this, so the new value is checked for newlines.
+    pub value: String,
+    /// How the value is written into a LaTeX table (e.g. as code or maths).
+    pub content: Content,
+    /// Write a `Content::Text` value into the table as-is, without escaping
+    /// it. This lets you embed arbitrary LaTeX in a cell.
+    pub raw: bool,
+    /// The number of columns this cell spans and the column specification
+    /// used for them (e.g. `c` or `|c|`), if it is a `\multicolumn` cell.
+    pub multicolumn: Option<(usize, String)>,
+    /// The number of rows this cell spans and its width, if it is a
+    /// `\multirow` cell.
+    pub multirow: Option<(usize, String)>,
+    /// The vertical alignment and width of the cell, if it is a paragraph
+    /// which should be aligned differently to the rest of its column.
+    pub paragraph: Option<(VAlign, String)>,
+    /// The cell's background colour. This takes precedence over the row's
+    /// background colour.
+    pub background: Option<String>,
+    /// The colour of the cell's text (using `\textcolor`), which goes around
+    /// any other styles.
+    pub text_color: Option<String>,
+    /// How the cell's text should be styled.
+    pub style: CellStyle,
+    /// A table which is rendered inside this cell instead of its value.
+    pub nested: Option<Box<Table>>,
+    /// The URL the cell's text links to (using `hyperref`'s `\href`).
+    pub link: Option<String>,
+}
+
+/// What non-finite numbers are rendered as.
+const MISSING_NUMBER: &str = "--";
+
+impl Cell {
+    /// Create a plain cell, making sure its value doesn't contain any
+    /// newlines.
+    pub fn new(value: String) -> Result<Cell, TableError> {
+        let mut cell = Cell::default();
+        cell.set_value(value)?;
+        Ok(cell)
+    }
+
+    /// Replace the cell's value, making sure the new value doesn't contain
+    /// any newlines. The cell is left unchanged if it does.
+    ///
+    /// Cells whose LaTeX is kept in their `content` (lines, segments and
+    /// symbols) become plain text again, so the new value is what's shown.
+    pub fn set_value(&mut self, value: String) -> Result<(), TableError> {
+        if value.contains('\n') || value.contains('\r') {
+            return Err(TableError::CellContainsNewline);
+        }
+
+        match self.content {
+            Content::Lines(_) | Content::Segments(_) | Content::Symbol(_) => {
+                self.content = Content::Text;
+            }
+            Content::Text | Content::Code | Content::Math => {}
+        }
+        self.value = value;
+        Ok(())
+    }
+
+    /// Create a cell which spans several columns, using a single column type
+    /// (e.g. `'c'`) for the combined column.
+    pub fn multicolumn(span: usize, alignment: char, value: String) -> Cell {
+        Cell::multicolumn_with_spec(span, &alignment.to_string(), value)
+    }
+
+    /// Create a cell which spans several columns, using a full column
+    /// specification (e.g. `|c|` to draw vertical rules either side).
+    pub fn multicolumn_with_spec(span: usize, spec: &str, value: String) -> Cell {
+        Cell {
+            value,
+            multicolumn: Some((span, spec.to_string())),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell which spans several rows, using `*` as the `width` to
+    /// let LaTeX pick the cell's natural width.
+    ///
+    /// The `multirow` package must be loaded to use these cells, which is
+    /// done for you by `Table::prepare_document()`.
+    ///
+    /// > **Note:** The rows being spanned over still need a cell in this
+    /// > column so their column counts line up. Use an empty cell (e.g.
+    /// > `Cell::default()`) as a placeholder.
+    pub fn multirow(span: usize, width: &str, value: String) -> Cell {
+        Cell {
+            value,
+            multirow: Some((span, width.to_string())),
+            ..Default::default()
+        }
+    }
+
+    /// Create a paragraph cell with its own width and vertical alignment.
+    ///
+    /// This is rendered as a single column `\multicolumn` (e.g.
+    /// `\multicolumn{1}{m{3cm}}{...}`), so it still only takes up one column.
+    pub fn paragraph(value: String, width: &str, valign: VAlign) -> Cell {
+        Cell {
+            value,
+            paragraph: Some((valign, width.to_string())),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell whose text is split over several lines. Each line is
+    /// escaped and they are joined with `\newline` so they don't end the
+    /// row.
+    ///
+    /// > **Note:** Line breaks only work in paragraph columns (`p`, `m`, `b`
+    /// > or `X`).
+    pub fn multiline(lines: Vec<String>) -> Cell {
+        Cell {
+            value: lines.join(" "),
+            content: Content::Lines(lines),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell containing another table.
+    ///
+    /// The inner table should normally be a plain `Tabular` without a
+    /// caption, label or placement, since floats can't go inside a table.
+    pub fn nested(table: Table) -> Cell {
+        Cell {
+            nested: Some(Box::new(table)),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell containing a short snippet of code, typeset in a
+    /// monospaced font with `\texttt`.
+    ///
+    /// Unlike `raw` cells the snippet is made safe for LaTeX, and unlike
+    /// normal cells runs of dashes are kept as-is instead of becoming en or
+    /// em dashes.
+    pub fn code(value: String) -> Cell {
+        Cell {
+            value,
+            content: Content::Code,
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell containing inline maths (e.g. `x^2`), wrapped in `$...$`.
+    ///
+    /// The formula is written as-is so it can use maths commands, except any
+    /// unescaped `$` is escaped so it can't end the maths early. No package
+    /// is needed for plain formulas, but `amsmath` is recommended.
+    pub fn math(latex: String) -> Cell {
+        Cell {
+            value: latex,
+            content: Content::Math,
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell from a mix of plain text and raw LaTeX, e.g. to put a
+    /// non-breaking space (`~`) or `\quad` between two bits of text.
+    ///
+    /// `Segment::Text` is escaped as usual while `Segment::Raw` is written
+    /// as-is. Only the text segments make up the cell's `value`.
+    pub fn from_segments(segments: Vec<Segment>) -> Cell {
+        let value = segments
+            .iter()
+            .filter_map(|segment| match *segment {
+                Segment::Text(ref text) => Some(text.as_str()),
+                Segment::Raw(_) => None,
+            })
+            .collect();
+
+        Cell {
+            value,
+            content: Content::Segments(segments),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell whose text is a hyperlink to `url` (using `\href`).
+    ///
+    /// The text is escaped as usual, while only the characters which would
+    /// break the link (`%` and `#`) are escaped in the URL. This requires the
+    /// `hyperref` package, which is added for you by
+    /// `Table::prepare_document()`.
+    pub fn href(url: String, text: String) -> Cell {
+        Cell {
+            value: text,
+            link: Some(url),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell containing a symbol, e.g. a tick or a cross for a table
+    /// of test results.
+    ///
+    /// Any package the symbol needs is added for you by
+    /// `Table::prepare_document()`.
+    pub fn symbol(symbol: Symbol) -> Cell {
+        Cell {
+            value: symbol.text().to_string(),
+            content: Content::Symbol(symbol),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell with a background colour (using `\cellcolor`).
+    ///
+    /// This requires the `xcolor` package to be loaded with the `table`
+    /// option, which is done for you by `Table::prepare_document()`.
+    pub fn with_background(value: String, color: &str) -> Cell {
+        Cell {
+            value,
+            background: Some(color.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell with coloured text (using `\textcolor`), e.g. a red
+    /// "FAILED".
+    ///
+    /// This can be combined with the `style` (e.g. bold and red). It requires
+    /// the `xcolor` package, which is added for you by
+    /// `Table::prepare_document()`.
+    pub fn colored_text(value: String, color: &str) -> Cell {
+        Cell {
+            value,
+            text_color: Some(color.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell with styled text (e.g. bold or italic).
+    pub fn formatted(value: String, style: CellStyle) -> Cell {
+        Cell {
+            value,
+            style,
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell containing a number, formatted so it can be aligned by a
+    /// `siunitx` `S` column.
+    ///
+    /// The number is always written with a `.` as the decimal separator and
+    /// without any thousands separators, regardless of locale. `-0` is
+    /// written as `0`, and NaN and infinite values are written as an en dash
+    /// (`--`) because they aren't numbers `siunitx` can typeset.
+    pub fn numeric(value: f64) -> Cell {
+        let value = if !value.is_finite() {
+            MISSING_NUMBER.to_string()
+        } else if value == 0.0 {
+            // catches -0.0 too
+            "0".to_string()
+        } else {
+            value.to_string()
+        };
+
+        Cell {
+            value,
+            ..Default::default()
+        }
+    }
+
+    /// Create a cell containing a number with exactly `precision` decimal
+    /// places (e.g. for a column of prices), to put in an `r` or `S` column.
+    ///
+    /// Like `numeric()` the number is formatted the same regardless of
+    /// locale, and it is never written in scientific notation, however big
+    /// or small it is. Values which round to zero don't get a minus sign, and
+    /// NaN and infinite values are written as `--`.
+    pub fn number(value: f64, precision: usize) -> Cell {
+        if !value.is_finite() {
+            return Cell::numeric(value);
+        }
+
+        let mut formatted = format!("{:.*}", precision, value);
+        if formatted.starts_with('-') && formatted[1..].chars().all(|c| c == '0' || c == '.') {
+            formatted.remove(0);
+        }
+
+        Cell {
+            value: formatted,
+            ..Default::default()
+        }
+    }
+
+    /// The number of table columns this cell takes up.
+    pub fn column_span(&self) -> usize {
+        match self.multicolumn {
+            Some((span, _)) => span,
+            None => 1,
+        }
+    }
+
+    /// Write the cell's value, wrapped in any styling commands.
+    fn fmt_text(&self, f: &mut Formatter) -> fmt::Result {
+        let commands = [
+            (self.style.bold, r"\textbf"),
+            (self.style.italic, r"\textit"),
+            (self.style.underline, r"\underline"),
+        ];
+        let mut depth = 0;
+
+        if let Some(ref color) = self.text_color {
+            write!(f, r"\textcolor{{{}}}{{", color)?;
+            depth += 1;
+        }
+
+        for &(enabled, command) in &commands {
+            if enabled {
+                write!(f, "{}{{", command)?;
+                depth += 1;
+            }
+        }
+
+        self.fmt_content(f)?;
+
+        for _ in 0..depth {
+            write!(f, "}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Cell {
+    /// Write the cell's value as LaTeX, without any styling.
+    pub(super) fn fmt_content<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        match self.content {
+            Content::Text if self.raw => write!(f, "{}", self.value),
+            Content::Text => write!(f, "{}", escape(&self.value)),
+            Content::Lines(ref lines) => {
+                let escaped: Vec<String> = lines.iter().map(|line| escape(line)).collect();
+                write!(f, "{}", escaped.join(r"\newline "))
+            }
+            Content::Code => write!(f, r"\texttt{{{}}}", escape_code(&self.value)),
+            Content::Math => write!(f, "${}$", escape_math(&self.value)),
+            Content::Segments(ref segments) => {
+                for segment in segments {
+                    match *segment {
+                        Segment::Text(ref text) => write!(f, "{}", escape(text))?,
+                        Segment::Raw(ref latex) => write!(f, "{}", latex)?,
+                    }
+                }
+                Ok(())
+            }
+            Content::Symbol(symbol) => write!(f, "{}", symbol.command()),
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some((span, ref spec)) = self.multicolumn {
+            write!(f, r"\multicolumn{{{}}}{{{}}}{{", span, spec)?;
+        } else if let Some((valign, ref width)) = self.paragraph {
+            write!(
+                f,
+                r"\multicolumn{{1}}{{{}{{{}}}}}{{",
+                valign.column_type(),
+                width
+            )?;
+        }
+        if let Some(ref color) = self.background {
+            write!(f, r"\cellcolor{{{}}}", color)?;
+        }
+        if let Some((span, ref width)) = self.multirow {
+            write!(f, r"\multirow{{{}}}{{{}}}{{", span, width)?;
+        }
+
+        match (&self.nested, &self.link) {
+            (Some(table), _) => write!(f, "{}", table)?,
+            (None, Some(url)) => {
+                write!(f, r"\href{{{}}}{{", escape_url(url))?;
+                self.fmt_text(f)?;
+                write!(f, "}}")?;
+            }
+            (None, None) => self.fmt_text(f)?,
+        }
+
+        if self.multirow.is_some() {
+            write!(f, "}}")?;
+        }
+        if self.multicolumn.is_some() || self.paragraph.is_some() {
+            write!(f, "}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a str> for Cell {
+    /// Create a plain cell from a string.
+    ///
+    /// Unlike `Cell::new()` the value isn't checked for newlines.
+    fn from(other: &'a str) -> Self {
+        Cell::from(other.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    /// Create a plain cell from a string.
+    ///
+    /// Unlike `Cell::new()` the value isn't checked for newlines.
+    fn from(other: String) -> Self {
+        Cell {
+            value: other,
+            ..Default::default()
+        }
+    }
+}
+
+/// The styles which can be applied to a cell's text.
+///
+/// When several styles are used they are nested with bold on the outside,
+/// then italics, then underlining (e.g. `\textbf{\textit{\underline{...}}}`).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CellStyle {
+    /// Bold text (`\textbf`).
+    pub bold: bool,
+    /// Italicized text (`\textit`).
+    pub italic: bool,
+    /// Underlined text (`\underline`).
+    pub underline: bool,
+}
+
+/// The vertical alignment of a paragraph cell.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VAlign {
+    /// Align the top of the cell with the rest of the row (a `p` column).
+    Top,
+    /// Centre the cell vertically (an `m` column).
+    Middle,
+    /// Align the bottom of the cell with the rest of the row (a `b` column).
+    Bottom,
+}
+
+impl VAlign {
+    /// The paragraph column type with this alignment.
+    pub fn column_type(&self) -> char {
+        match *self {
+            VAlign::Top => 'p',
+            VAlign::Middle => 'm',
+            VAlign::Bottom => 'b',
+        }
+    }
+}
+
+/// How a cell's value is written into a LaTeX table.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Content {
+    /// Plain text, which is escaped unless the cell is `raw`.
+    #[default]
+    Text,
+    /// Several lines of text, joined with `\newline` (see
+    /// `Cell::multiline()`).
+    Lines(Vec<String>),
+    /// A code snippet in a monospaced font (see `Cell::code()`).
+    Code,
+    /// Inline maths (see `Cell::math()`).
+    Math,
+    /// A mix of text and raw LaTeX (see `Cell::from_segments()`).
+    Segments(Vec<Segment>),
+    /// A symbol (see `Cell::symbol()`).
+    Symbol(Symbol),
+}
+
+/// A symbol for use with `Cell::symbol()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Symbol {
+    /// A tick (`\checkmark`).
+    Check,
+    /// A multiplication sign used as a cross (`$\times$`).
+    Cross,
+    /// An en dash (`--`), e.g. for a missing value.
+    Dash,
+    /// A bullet point (`\textbullet`).
+    Bullet,
+}
+
+impl Symbol {
+    /// The LaTeX used to draw the symbol.
+    pub fn command(&self) -> &'static str {
+        match *self {
+            Symbol::Check => r"\checkmark",
+            Symbol::Cross => r"$\times$",
+            Symbol::Dash => "--",
+            Symbol::Bullet => r"\textbullet",
+        }
+    }
+
+    /// The symbol as plain text, for exporting the table to something other
+    /// than LaTeX.
+    pub fn text(&self) -> &'static str {
+        match *self {
+            Symbol::Check => "\u{2713}",
+            Symbol::Cross => "\u{D7}",
+            Symbol::Dash => "\u{2013}",
+            Symbol::Bullet => "\u{2022}",
+        }
+    }
+
+    /// The package which defines the symbol, if it isn't built into LaTeX.
+    pub fn package(&self) -> Option<&'static str> {
+        match *self {
+            Symbol::Check => Some("amssymb"),
+            Symbol::Cross | Symbol::Dash | Symbol::Bullet => None,
+        }
+    }
+}
+
+/// Part of a cell created with `Cell::from_segments()`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Segment {
+    /// Plain text, which gets escaped.
+    Text(String),
+    /// LaTeX which is written as-is (e.g. `~` or `\quad`).
+    Raw(String),
+}
+
+/// Escape any characters which have a special meaning in LaTeX.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Escape a code snippet so it can go inside `\texttt`.
+fn escape_code(text: &str) -> String {
+    // stop "--" and "---" from being turned into dashes
+    escape(text).replace('-', "-{}")
+}
+
+/// Escape the characters which would break an `\href` URL.
+fn escape_url(url: &str) -> String {
+    url.replace('%', r"\%").replace('#', r"\#")
+}
+
+/// Escape every `$` which isn't already escaped.
+fn escape_math(formula: &str) -> String {
+    let mut escaped = String::with_capacity(formula.len());
+    let mut after_backslash = false;
+
+    for c in formula.chars() {
+        if c == '$' && !after_backslash {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        after_backslash = c == '\\' && !after_backslash;
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tables::TableKind;
+
+    #[test]
+    fn escape_special_characters() {
+        let should_be =
+            r"\& \% \$ \# \_ \{ \} \textasciitilde{} \textasciicircum{} \textbackslash{}";
+        let cell = Cell {
+            value: r"& % $ # _ { } ~ ^ \".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(cell.to_string(), should_be);
+    }
+
+    #[test]
+    fn percentages_arent_comments() {
+        let cell = Cell {
+            value: "50% off".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(cell.to_string(), r"50\% off");
+    }
+
+    #[test]
+    fn raw_cells_arent_escaped() {
+        let cell = Cell {
+            value: r"\textbf{50%}".to_string(),
+            raw: true,
+            ..Default::default()
+        };
+
+        assert_eq!(cell.to_string(), r"\textbf{50%}");
+    }
+
+    #[test]
+    fn create_a_checked_cell() {
+        let cell = Cell::new("Hello World".to_string()).unwrap();
+
+        assert_eq!(cell.value, "Hello World");
+        assert!(!cell.raw);
+    }
+
+    #[test]
+    fn cells_cant_contain_newlines() {
+        for value in &["Hello\nWorld", "Hello\rWorld"] {
+            let got = Cell::new(value.to_string()).unwrap_err();
+
+            assert_eq!(got, TableError::CellContainsNewline);
+        }
+    }
+
+    #[test]
+    fn set_a_cells_value() {
+        let mut cell = Cell::multicolumn(2, 'c', "old".to_string());
+
+        cell.set_value("new".to_string()).unwrap();
+
+        assert_eq!(cell.value, "new");
+        assert_eq!(cell.column_span(), 2);
+    }
+
+    #[test]
+    fn set_value_rejects_newlines() {
+        let mut cell = Cell::new("old".to_string()).unwrap();
+
+        let got = cell.set_value("line 1\nline 2".to_string()).unwrap_err();
+
+        assert_eq!(got, TableError::CellContainsNewline);
+        assert_eq!(cell.value, "old");
+    }
+
+    #[test]
+    fn render_multicolumn_cell() {
+        let cell = Cell::multicolumn(3, 'c', "Results & Discussion".to_string());
+
+        assert_eq!(
+            cell.to_string(),
+            r"\multicolumn{3}{c}{Results \& Discussion}"
+        );
+        assert_eq!(cell.column_span(), 3);
+    }
+
+    #[test]
+    fn render_multicolumn_cell_with_borders() {
+        let cell = Cell::multicolumn_with_spec(2, "|c|", "Title".to_string());
+
+        assert_eq!(cell.to_string(), r"\multicolumn{2}{|c|}{Title}");
+        assert_eq!(cell.column_span(), 2);
+    }
+
+    #[test]
+    fn render_cell_with_background() {
+        let cell = Cell::with_background("FAILED".to_string(), "red!30");
+
+        assert_eq!(cell.to_string(), r"\cellcolor{red!30}FAILED");
+    }
+
+    #[test]
+    fn background_goes_inside_multicolumn() {
+        let mut cell = Cell::multicolumn(2, 'c', "Title".to_string());
+        cell.background = Some("blue".to_string());
+
+        assert_eq!(
+            cell.to_string(),
+            r"\multicolumn{2}{c}{\cellcolor{blue}Title}"
+        );
+    }
+
+    #[test]
+    fn render_bold_cell() {
+        let style = CellStyle {
+            bold: true,
+            ..Default::default()
+        };
+        let cell = Cell::formatted("R&D".to_string(), style);
+
+        assert_eq!(cell.to_string(), r"\textbf{R\&D}");
+    }
+
+    #[test]
+    fn render_cell_with_every_style() {
+        let style = CellStyle {
+            bold: true,
+            italic: true,
+            underline: true,
+        };
+        let cell = Cell::formatted("Total".to_string(), style);
+
+        assert_eq!(cell.to_string(), r"\textbf{\textit{\underline{Total}}}");
+    }
+
+    #[test]
+    fn render_italic_underlined_cell() {
+        let style = CellStyle {
+            italic: true,
+            underline: true,
+            ..Default::default()
+        };
+        let cell = Cell::formatted("note".to_string(), style);
+
+        assert_eq!(cell.to_string(), r"\textit{\underline{note}}");
+    }
+
+    #[test]
+    fn render_numeric_cells() {
+        assert_eq!(Cell::numeric(1234567.5).to_string(), "1234567.5");
+        assert_eq!(Cell::numeric(-0.25).to_string(), "-0.25");
+        assert_eq!(Cell::numeric(3.0).to_string(), "3");
+        assert_eq!(Cell::numeric(-0.0).to_string(), "0");
+    }
+
+    #[test]
+    fn non_finite_numbers_become_dashes() {
+        for &value in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(Cell::numeric(value).to_string(), "--");
+            assert_eq!(Cell::number(value, 2).to_string(), "--");
+        }
+    }
+
+    #[test]
+    fn render_multirow_cell() {
+        let cell = Cell::multirow(2, "*", "Group A".to_string());
+
+        assert_eq!(cell.to_string(), r"\multirow{2}{*}{Group A}");
+        assert_eq!(cell.column_span(), 1);
+    }
+
+    #[test]
+    fn render_multirow_with_fixed_width() {
+        let cell = Cell::multirow(3, "2cm", "Group_B".to_string());
+
+        assert_eq!(cell.to_string(), r"\multirow{3}{2cm}{Group\_B}");
+    }
+
+    #[test]
+    fn render_paragraph_cells() {
+        let inputs = vec![
+            (VAlign::Top, r"\multicolumn{1}{p{3cm}}{Top}"),
+            (VAlign::Middle, r"\multicolumn{1}{m{3cm}}{Middle}"),
+            (VAlign::Bottom, r"\multicolumn{1}{b{3cm}}{Bottom}"),
+        ];
+
+        for (valign, should_be) in inputs {
+            let value = format!("{:?}", valign);
+            let cell = Cell::paragraph(value, "3cm", valign);
+
+            assert_eq!(cell.to_string(), should_be);
+            assert_eq!(cell.column_span(), 1);
+        }
+    }
+
+    #[test]
+    fn render_multiline_cell() {
+        let lines = vec!["First line".to_string(), "50% of the second".to_string()];
+
+        let cell = Cell::multiline(lines);
+
+        assert_eq!(cell.to_string(), r"First line\newline 50\% of the second");
+        assert!(!cell.to_string().contains(r"\\"));
+    }
+
+    #[test]
+    fn cells_from_strings() {
+        let from_str: Cell = "50%".into();
+        let from_string: Cell = "50%".to_string().into();
+
+        assert_eq!(from_str, from_string);
+        assert_eq!(from_str.value, "50%");
+        assert_eq!(from_str.to_string(), r"50\%");
+    }
+
+    #[test]
+    fn render_nested_table() {
+        let mut inner = Table::new(TableKind::Tabular, "textwidth", "l");
+        inner.push_row_values(vec!["x"]).unwrap();
+
+        let cell = Cell::nested(inner);
+
+        let should_be = r"\begin{tabular}{l}
+x\\
+\end{tabular}";
+
+        assert_eq!(cell.to_string(), should_be);
+        assert_eq!(cell.column_span(), 1);
+    }
+
+    #[test]
+    fn render_code_cell() {
+        let cell = Cell::code(r#"printf("50%\n"); x--; {}"#.to_string());
+
+        assert_eq!(
+            cell.to_string(),
+            r#"\texttt{printf("50\%\textbackslash{}n"); x-{}-{}; \{\}}"#
+        );
+    }
+
+    #[test]
+    fn math_cells_arent_escaped() {
+        let cell = Cell::math("x^2 + y_{i}".to_string());
+
+        assert_eq!(cell.to_string(), "$x^2 + y_{i}$");
+    }
+
+    #[test]
+    fn math_cells_cant_end_the_maths_early() {
+        let cell = Cell::math(r"5\$ + x$ = \\$".to_string());
+
+        assert_eq!(cell.to_string(), r"$5\$ + x\$ = \\\$$");
+    }
+
+    #[test]
+    fn dollars_in_plain_cells_are_escaped() {
+        let cell = Cell::from("$5");
+
+        assert_eq!(cell.to_string(), r"\$5");
+    }
+
+    #[test]
+    fn mix_text_and_raw_segments() {
+        let cell = Cell::from_segments(vec![
+            Segment::Text("50%".to_string()),
+            Segment::Raw("~".to_string()),
+            Segment::Text("off~".to_string()),
+            Segment::Raw(r"\quad".to_string()),
+        ]);
+
+        assert_eq!(cell.to_string(), r"50\%~off\textasciitilde{}\quad");
+    }
+
+    #[test]
+    fn render_hyperlink() {
+        let cell = Cell::href(
+            "https://example.com/a%20b#top".to_string(),
+            "Docs & more".to_string(),
+        );
+
+        assert_eq!(
+            cell.to_string(),
+            r"\href{https://example.com/a\%20b\#top}{Docs \& more}"
+        );
+    }
+
+    #[test]
+    fn format_numbers_with_a_fixed_precision() {
+        let values: Vec<String> = vec![
+            Cell::number(1234.6, 0),
+            Cell::number(12.345678, 2),
+            Cell::number(-2.5, 2),
+            Cell::number(1e21, 1),
+            Cell::number(-0.001, 2),
+        ]
+        .into_iter()
+        .map(|cell| cell.to_string())
+        .collect();
+
+        assert_eq!(
+            values,
+            vec!["1235", "12.35", "-2.50", "1000000000000000000000.0", "0.00"]
+        );
+    }
+
+    #[test]
+    fn render_colored_text() {
+        let mut cell = Cell::colored_text("FAILED_1".to_string(), "red");
+        assert_eq!(cell.to_string(), r"\textcolor{red}{FAILED\_1}");
+
+        cell.style.bold = true;
+
+        assert_eq!(cell.to_string(), r"\textcolor{red}{\textbf{FAILED\_1}}");
+    }
+
+    #[test]
+    fn render_symbols() {
+        let symbols = [
+            (Symbol::Check, r"\checkmark", Some("amssymb")),
+            (Symbol::Cross, r"$\times$", None),
+            (Symbol::Dash, "--", None),
+            (Symbol::Bullet, r"\textbullet", None),
+        ];
+
+        for &(symbol, should_be, package) in &symbols {
+            let cell = Cell::symbol(symbol);
+
+            assert_eq!(cell.to_string(), should_be);
+            assert_eq!(symbol.package(), package);
+        }
+    }
+
+    #[test]
+    fn values_hold_the_plain_text() {
+        let cells = [
+            (
+                Cell::multiline(vec!["a_b".to_string(), "c".to_string()]),
+                "a_b c",
+            ),
+            (Cell::code("x--".to_string()), "x--"),
+            (Cell::math("x^2".to_string()), "x^2"),
+            (
+                Cell::from_segments(vec![
+                    Segment::Text("10".to_string()),
+                    Segment::Raw("~".to_string()),
+                    Segment::Text("km".to_string()),
+                ]),
+                "10km",
+            ),
+            (Cell::symbol(Symbol::Check), "\u{2713}"),
+        ];
+
+        for &(ref cell, should_be) in &cells {
+            assert_eq!(cell.value, should_be);
+            assert!(!cell.raw);
+        }
+    }
+
+    #[test]
+    fn setting_the_value_of_a_symbol_makes_it_text() {
+        let mut cell = Cell::symbol(Symbol::Check);
+
+        cell.set_value("n/a".to_string()).unwrap();
+
+        assert_eq!(cell.content, Content::Text);
+        assert_eq!(cell.to_string(), "n/a");
+    }
+}
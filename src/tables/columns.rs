@@ -0,0 +1,424 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::mem;
+use std::str::Chars;
+
+use super::VAlign;
+
+/// A single entry in a table's column specification.
+///
+/// This is a structured alternative to writing the specification string by
+/// hand, and renders to the same syntax (e.g. `Column::Center` is `c`).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Column {
+    /// A left-aligned column (`l`).
+    Left,
+    /// A centred column (`c`).
+    Center,
+    /// A right-aligned column (`r`).
+    Right,
+    /// A paragraph column with a fixed width (`p{...}`, `m{...}` or
+    /// `b{...}`, depending on its vertical alignment).
+    Para {
+        /// The column's width (e.g. `3cm`).
+        width: String,
+        /// How the column's cells are aligned vertically.
+        valign: VAlign,
+    },
+    /// A `tabularx` column which stretches to fill the table (`X`).
+    X,
+    /// A `siunitx` column which aligns numbers on their decimal point (`S`).
+    Siunitx,
+    /// Replace the space between two columns with some text (`@{...}`). An
+    /// empty separator removes the padding altogether.
+    Separator(String),
+    /// A vertical rule between two columns (`|`).
+    VerticalRule,
+    /// Any other column type (e.g. one created with `\newcolumntype`).
+    Other(char),
+}
+
+impl Column {
+    /// Create a separator column (`@{...}`).
+    pub fn separator(text: &str) -> Column {
+        Column::Separator(text.to_string())
+    }
+
+    /// Is this entry drawn between columns rather than being a column
+    /// itself?
+    pub fn is_separator(&self) -> bool {
+        matches!(*self, Column::Separator(_) | Column::VerticalRule)
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Column::Left => write!(f, "l"),
+            Column::Center => write!(f, "c"),
+            Column::Right => write!(f, "r"),
+            Column::Para { ref width, valign } => {
+                write!(f, "{}{{{}}}", valign.column_type(), width)
+            }
+            Column::X => write!(f, "X"),
+            Column::Siunitx => write!(f, "S"),
+            Column::Separator(ref text) => write!(f, "@{{{}}}", text),
+            Column::VerticalRule => write!(f, "|"),
+            Column::Other(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+/// Render a list of columns as a column specification string.
+pub fn spec_from_columns(columns: &[Column]) -> String {
+    columns.iter().map(|c| c.to_string()).collect()
+}
+
+/// Parse a column specification into its columns, separators and vertical
+/// rules.
+///
+/// `>{...}` and `<{...}` decorators aren't represented by `Column`, so they
+/// are skipped.
+pub fn parse_columns(spec: &str) -> Vec<Column> {
+    let mut chars = spec.chars().peekable();
+    let mut parsed = Vec::new();
+
+    while let Some(c) = chars.next() {
+        let column = match c {
+            'l' => Column::Left,
+            'c' => Column::Center,
+            'r' => Column::Right,
+            'p' | 'm' | 'b' => {
+                let valign = match c {
+                    'p' => VAlign::Top,
+                    'm' => VAlign::Middle,
+                    _ => VAlign::Bottom,
+                };
+                Column::Para {
+                    width: strip_braces(&take_group(&mut chars)),
+                    valign,
+                }
+            }
+            'X' => Column::X,
+            'S' => Column::Siunitx,
+            '@' => Column::Separator(strip_braces(&take_group(&mut chars))),
+            '|' => Column::VerticalRule,
+            '>' | '<' => {
+                skip_group(&mut chars);
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            _ => Column::Other(c),
+        };
+
+        parsed.push(column);
+    }
+
+    parsed
+}
+
+/// Count the number of columns described by a column specification (e.g.
+/// `lp{3cm}|r`).
+pub fn count_columns(spec: &str) -> usize {
+    column_letters(spec).len()
+}
+
+/// Get the letter used for each column in a column specification.
+///
+/// Each of `l`, `c`, `r`, `X` and `S` is a single column, as is `p`, `m` or
+/// `b` followed by its `{width}` argument. Vertical rules (`|`), `@{...}`
+/// separators, `>{...}` and `<{...}` decorators and whitespace aren't columns.
+/// Anything else is assumed to be a single column.
+pub fn column_letters(spec: &str) -> Vec<char> {
+    let mut chars = spec.chars().peekable();
+    let mut letters = Vec::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            'p' | 'm' | 'b' => {
+                skip_group(&mut chars);
+                letters.push(c);
+            }
+            '@' | '>' | '<' => skip_group(&mut chars),
+            '|' => {}
+            c if c.is_whitespace() => {}
+            _ => letters.push(c),
+        }
+    }
+
+    letters
+}
+
+/// Find the first character in a column specification which isn't a known
+/// column type (`l`, `c`, `r`, `p`, `m`, `b`, `X` or `S`) or decorator (`|`,
+/// `@{...}`, `>{...}` or `<{...}`).
+pub fn find_invalid_column(spec: &str) -> Option<char> {
+    let mut chars = spec.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            'p' | 'm' | 'b' | '@' | '>' | '<' => skip_group(&mut chars),
+            'l' | 'c' | 'r' | 'X' | 'S' | '|' => {}
+            c if c.is_whitespace() => {}
+            _ => return Some(c),
+        }
+    }
+
+    None
+}
+
+/// Insert some text immediately before the columns with the provided
+/// indices, leaving the rest of the column specification untouched.
+///
+/// The text goes in front of any `>{...}` decorators on the column, so it
+/// doesn't end up between a decorator and the column it applies to.
+pub fn insert_before_columns(spec: &str, insertions: &[(usize, String)]) -> String {
+    let mut chars = spec.chars().peekable();
+    let mut rendered = String::with_capacity(spec.len());
+    let mut column = 0;
+    // where the ">" decorators in front of the next column start
+    let mut decorators_start = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '>' => {
+                decorators_start.get_or_insert(rendered.len());
+                rendered.push(c);
+                rendered.push_str(&take_group(&mut chars));
+            }
+            '@' | '<' => {
+                rendered.push(c);
+                rendered.push_str(&take_group(&mut chars));
+            }
+            '|' => rendered.push(c),
+            c if c.is_whitespace() => rendered.push(c),
+            _ => {
+                let inserted: String = insertions
+                    .iter()
+                    .filter(|&&(i, _)| i == column)
+                    .map(|(_, text)| text.as_str())
+                    .collect();
+                let position = decorators_start.take().unwrap_or(rendered.len());
+                rendered.insert_str(position, &inserted);
+
+                rendered.push(c);
+                if c == 'p' || c == 'm' || c == 'b' {
+                    rendered.push_str(&take_group(&mut chars));
+                }
+                column += 1;
+            }
+        }
+    }
+
+    rendered
+}
+
+/// Split a column specification into the text for each column, returned
+/// along with whatever comes after the last column (e.g. a closing `|`).
+///
+/// Each column's text starts with the rules, separators and `>{...}`
+/// decorators in front of it and ends with any `<{...}` decorators after it,
+/// so joining everything back together gives the original specification.
+pub fn split_spec(spec: &str) -> (Vec<String>, String) {
+    let mut chars = spec.chars().peekable();
+    let mut pieces: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '<' => {
+                let group = take_group(&mut chars);
+                // a "<" decorator belongs to the column before it
+                let piece = match pieces.last_mut() {
+                    Some(previous) if current.is_empty() => previous,
+                    _ => &mut current,
+                };
+                piece.push(c);
+                piece.push_str(&group);
+            }
+            '@' | '>' => {
+                current.push(c);
+                current.push_str(&take_group(&mut chars));
+            }
+            '|' => current.push(c),
+            c if c.is_whitespace() => current.push(c),
+            _ => {
+                current.push(c);
+                if c == 'p' || c == 'm' || c == 'b' {
+                    current.push_str(&take_group(&mut chars));
+                }
+                pieces.push(mem::take(&mut current));
+            }
+        }
+    }
+
+    (pieces, current)
+}
+
+/// Remove the outer braces from a group taken by `take_group()`.
+fn strip_braces(group: &str) -> String {
+    let inner = group.strip_prefix('{').unwrap_or(group);
+    inner.strip_suffix('}').unwrap_or(inner).to_string()
+}
+
+/// Skip past a braced group (e.g. `{3cm}`).
+fn skip_group(chars: &mut Peekable<Chars>) {
+    take_group(chars);
+}
+
+/// Consume a braced group (e.g. `{3cm}`), taking nested braces into account.
+/// Nothing is consumed if the next character isn't a `{`.
+fn take_group(chars: &mut Peekable<Chars>) -> String {
+    let mut group = String::new();
+    if chars.peek() != Some(&'{') {
+        return group;
+    }
+
+    let mut depth = 0;
+
+    for c in chars {
+        group.push(c);
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    group
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_simple_columns() {
+        assert_eq!(count_columns("lcrXS"), 5);
+    }
+
+    #[test]
+    fn count_paragraph_columns() {
+        assert_eq!(count_columns("llp{3cm}r"), 4);
+    }
+
+    #[test]
+    fn vertical_rules_arent_columns() {
+        assert_eq!(count_columns("|l|X|"), 2);
+    }
+
+    #[test]
+    fn separators_arent_columns() {
+        assert_eq!(count_columns("m{2cm}@{}c"), 2);
+    }
+
+    #[test]
+    fn decorators_arent_columns() {
+        assert_eq!(count_columns(r">{\bfseries}lc"), 2);
+        assert_eq!(column_letters(r">{\bfseries}l<{\%}c"), vec!['l', 'c']);
+    }
+
+    #[test]
+    fn insert_text_before_decorators() {
+        let insertions = vec![(0, "|".to_string()), (1, "|".to_string())];
+
+        let got = insert_before_columns(r">{\bfseries}l<{x}>{\itshape}c", &insertions);
+
+        assert_eq!(got, r"|>{\bfseries}l<{x}|>{\itshape}c");
+    }
+
+    #[test]
+    fn split_a_spec_into_columns() {
+        let (pieces, rest) = split_spec(r"|l|>{\bfseries}c<{x}@{}p{2cm}|");
+
+        assert_eq!(pieces, vec!["|l", r"|>{\bfseries}c<{x}", "@{}p{2cm}"]);
+        assert_eq!(rest, "|");
+    }
+
+    #[test]
+    fn letters_for_each_column() {
+        assert_eq!(column_letters("|l|p{3cm}@{}r|"), vec!['l', 'p', 'r']);
+    }
+
+    #[test]
+    fn valid_column_types() {
+        assert_eq!(
+            find_invalid_column(r"|l|c|r|p{1cm}m{2cm}b{3cm}XS@{}>{\bfseries}l<{x}|"),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_column_type() {
+        assert_eq!(find_invalid_column("lXz"), Some('z'));
+    }
+
+    #[test]
+    fn braced_arguments_arent_validated() {
+        assert_eq!(find_invalid_column(r"p{\linewidth}"), None);
+    }
+
+    #[test]
+    fn insert_text_before_columns() {
+        let insertions = vec![(1, "<".to_string()), (2, ">".to_string())];
+
+        let got = insert_before_columns("|l|p{3cm}@{ }r|", &insertions);
+
+        assert_eq!(got, "|l|<p{3cm}@{ }>r|");
+    }
+
+    #[test]
+    fn spec_from_structured_columns() {
+        let columns = vec![
+            Column::VerticalRule,
+            Column::Left,
+            Column::separator(""),
+            Column::Para {
+                width: "3cm".to_string(),
+                valign: VAlign::Middle,
+            },
+            Column::separator(" -- "),
+            Column::Right,
+            Column::X,
+            Column::Siunitx,
+            Column::Center,
+            Column::VerticalRule,
+        ];
+
+        let spec = spec_from_columns(&columns);
+
+        assert_eq!(spec, "|l@{}m{3cm}@{ -- }rXSc|");
+        assert_eq!(count_columns(&spec), 6);
+        assert_eq!(columns.iter().filter(|c| !c.is_separator()).count(), 6);
+    }
+
+    #[test]
+    fn parse_structured_columns() {
+        let should_be = vec![
+            Column::VerticalRule,
+            Column::Left,
+            Column::Separator(String::new()),
+            Column::Para {
+                width: r"\dimexpr{3cm}".to_string(),
+                valign: VAlign::Top,
+            },
+            Column::Center,
+            Column::Other('Y'),
+        ];
+
+        assert_eq!(
+            parse_columns(r"|l@{}p{\dimexpr{3cm}}>{\bfseries}c Y"),
+            should_be
+        );
+    }
+
+    #[test]
+    fn nested_braces_in_width() {
+        assert_eq!(count_columns(r"p{\dimexpr{3cm}}l"), 2);
+    }
+}
@@ -0,0 +1,444 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::{Row, Table, TableError, TableKind};
+
+const KINDS: [TableKind; 4] = [
+    TableKind::Tabular,
+    TableKind::Tabularx,
+    TableKind::LongTable,
+    TableKind::XLTabular,
+];
+
+impl Table {
+    /// Create a table from a TOML config file.
+    ///
+    /// The config uses these keys, of which only `column_types` is required:
+    ///
+    /// ```toml
+    /// kind = "tabularx"          # the environment name, "tabular" by default
+    /// table_width = "linewidth"  # "textwidth" by default
+    /// column_types = "lX"
+    /// header_rows = 1            # how many rows at the top are headers
+    /// rows = [
+    ///     ["Planet", "Description"],
+    ///     ["Mercury", "The smallest planet"],
+    /// ]
+    /// ```
+    ///
+    /// Every row is checked against `column_types`, and each key may only be
+    /// given once. Only this subset of TOML (strings, integers and arrays) is
+    /// understood.
+    ///
+    /// > **Note:** This requires the `table-config` feature.
+    pub fn from_config_str(src: &str) -> Result<Table, TableError> {
+        let mut kind = TableKind::Tabular;
+        let mut table_width = "textwidth".to_string();
+        let mut column_types = None;
+        let mut header_rows = 0;
+        let mut rows = Vec::new();
+
+        for (key, value) in Parser::new(src).parse()? {
+            match (key.as_str(), value) {
+                ("kind", Value::String(name)) => {
+                    kind = KINDS
+                        .iter()
+                        .find(|candidate| candidate.environment_name() == name)
+                        .cloned()
+                        .ok_or_else(|| config_error(format!("Unknown table kind \"{}\"", name)))?;
+                }
+                ("table_width", Value::String(width)) => table_width = width,
+                ("column_types", Value::String(spec)) => column_types = Some(spec),
+                ("header_rows", Value::Integer(count)) => header_rows = count,
+                ("rows", Value::Array(values)) => {
+                    rows = values
+                        .into_iter()
+                        .map(row_values)
+                        .collect::<Result<_, _>>()?;
+                }
+                (key, _) => {
+                    return Err(config_error(format!("Unexpected value for \"{}\"", key)));
+                }
+            }
+        }
+
+        let column_types =
+            column_types.ok_or_else(|| config_error("Missing \"column_types\"".to_string()))?;
+        let mut table = Table::new(kind, &table_width, &column_types);
+
+        for (i, values) in rows.into_iter().enumerate() {
+            table
+                .push_row(values.into_iter().collect::<Row>())
+                .map_err(|e| e.in_row(i))?;
+        }
+        table.set_header_rows(header_rows);
+
+        Ok(table)
+    }
+
+    /// Write the table's kind, columns and rows in the format read by
+    /// `from_config_str()`.
+    ///
+    /// The format only has room for each cell's plain value and for header
+    /// rows at the top of the table. Rather than silently leaving anything
+    /// out, a table using other settings (e.g. a caption or styled cells)
+    /// gives a `TableError::Config`, so reading the config back always gives
+    /// the same table.
+    ///
+    /// > **Note:** This requires the `table-config` feature.
+    pub fn to_config_string(&self) -> Result<String, TableError> {
+        let header_rows = self.rows.iter().take_while(|row| row.is_header).count();
+        let mut config = format!(
+            "kind = {}\ntable_width = {}\ncolumn_types = {}\nheader_rows = {}\nrows = [\n",
+            quote(self.kind.environment_name()),
+            quote(&self.table_width),
+            quote(&self.column_types),
+            header_rows
+        );
+
+        for row in &self.rows {
+            let values: Vec<String> = row.iter().map(|cell| quote(&cell.value)).collect();
+            config.push_str(&format!("    [{}],\n", values.join(", ")));
+        }
+
+        config.push_str("]\n");
+
+        match Table::from_config_str(&config) {
+            Ok(ref table) if table == self => Ok(config),
+            _ => Err(config_error(
+                "Only the kind, width, columns, header rows and cell values can be written"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+fn config_error(msg: String) -> TableError {
+    TableError::Config(msg)
+}
+
+fn row_values(value: Value) -> Result<Vec<String>, TableError> {
+    match value {
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(text) => Ok(text),
+                _ => Err(config_error("Cells must be strings".to_string())),
+            })
+            .collect(),
+        _ => Err(config_error("Rows must be arrays of strings".to_string())),
+    }
+}
+
+/// Write text as a TOML basic string.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    String(String),
+    Integer(usize),
+    Array(Vec<Value>),
+}
+
+/// A parser for the small subset of TOML used by table configs.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Parser<'a> {
+        Parser {
+            chars: src.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<(String, Value)>, TableError> {
+        let mut pairs = Vec::new();
+
+        loop {
+            self.skip_whitespace(true);
+            if self.chars.peek().is_none() {
+                break;
+            }
+
+            let key = self.key()?;
+            if pairs.iter().any(|(existing, _)| *existing == key) {
+                return Err(self.error(&format!("Duplicate key \"{}\"", key)));
+            }
+            self.skip_whitespace(false);
+            self.expect('=')?;
+            self.skip_whitespace(false);
+            let value = self.value()?;
+            pairs.push((key, value));
+
+            self.skip_whitespace(false);
+            match self.chars.peek() {
+                None | Some(&'\n') => {}
+                Some(_) => return Err(self.error("Expected a newline after the value")),
+            }
+        }
+
+        Ok(pairs)
+    }
+
+    fn key(&mut self) -> Result<String, TableError> {
+        let mut key = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                key.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if key.is_empty() {
+            Err(self.error("Expected a key"))
+        } else {
+            Ok(key)
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, TableError> {
+        match self.chars.peek() {
+            Some(&'"') => self.string().map(Value::String),
+            Some(&'[') => self.array().map(Value::Array),
+            Some(c) if c.is_ascii_digit() => self.integer().map(Value::Integer),
+            _ => Err(self.error("Expected a string, integer or array")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, TableError> {
+        self.expect('"')?;
+        let mut text = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => {
+                    let escaped = match self.chars.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('u') => self.unicode_escape()?,
+                        _ => return Err(self.error("Unknown escape sequence")),
+                    };
+                    text.push(escaped);
+                }
+                Some('\n') | None => return Err(self.error("Unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, TableError> {
+        let digits: String = (0..4).filter_map(|_| self.chars.next()).collect();
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(::std::char::from_u32)
+            .ok_or_else(|| self.error("Invalid unicode escape"))
+    }
+
+    fn integer(&mut self) -> Result<usize, TableError> {
+        let mut digits = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_digit() || c == '_' {
+                digits.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        digits
+            .replace('_', "")
+            .parse()
+            .map_err(|_| self.error("Invalid integer"))
+    }
+
+    fn array(&mut self) -> Result<Vec<Value>, TableError> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+
+        loop {
+            self.skip_whitespace(true);
+            if self.chars.peek() == Some(&']') {
+                self.chars.next();
+                return Ok(values);
+            }
+
+            values.push(self.value()?);
+
+            self.skip_whitespace(true);
+            match self.chars.next() {
+                Some(',') => {}
+                Some(']') => return Ok(values),
+                _ => return Err(self.error("Expected a \",\" or \"]\" in the array")),
+            }
+        }
+    }
+
+    /// Skip spaces and comments, and newlines too if `newlines` is set.
+    fn skip_whitespace(&mut self, newlines: bool) {
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                ' ' | '\t' | '\r' => {}
+                '\n' if newlines => self.line += 1,
+                '#' => {
+                    while self.chars.peek().is_some_and(|&c| c != '\n') {
+                        self.chars.next();
+                    }
+                    continue;
+                }
+                _ => return,
+            }
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TableError> {
+        if self.chars.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected a \"{}\"", expected)))
+        }
+    }
+
+    fn error(&self, msg: &str) -> TableError {
+        config_error(format!("{} on line {}", msg, self.line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tables::Cell;
+
+    #[test]
+    fn config_round_trip() {
+        let mut table = Table::new(TableKind::Tabularx, "linewidth", "l|X");
+        table
+            .push_row_values(vec!["Name", "Notes"])
+            .unwrap()
+            .push_row_values(vec!["Alice \"Al\"", "C:\\Users\\alice"])
+            .unwrap()
+            .push_row_values(vec!["Bob", "50% & more"])
+            .unwrap();
+        table.set_header_rows(1);
+
+        let config = table.to_config_string().unwrap();
+        let got = Table::from_config_str(&config).unwrap();
+
+        assert_eq!(got, table);
+        assert_eq!(got.column_count(), 2);
+        assert_eq!(got.to_string(), table.to_string());
+    }
+
+    #[test]
+    fn read_a_handwritten_config() {
+        let src = r#"
+# Planets
+kind = "longtable"
+column_types = "lr"   # name and radius
+rows = [
+    ["Mercury", "2440"],
+    ["Jupiter", "69911"],  # the biggest
+]
+"#;
+
+        let table = Table::from_config_str(src).unwrap();
+
+        assert_eq!(table.kind, TableKind::LongTable);
+        assert_eq!(table.table_width, "textwidth");
+        assert_eq!(table.column_count(), 2);
+        assert_eq!(table.rows.len(), 2);
+        assert!(!table.rows[0].is_header);
+        assert_eq!(table.rows[1].cells[1].value, "69911");
+    }
+
+    #[test]
+    fn config_rows_are_checked() {
+        let src = "column_types = \"ll\"\nrows = [[\"a\", \"b\"], [\"c\"]]\n";
+
+        let got = Table::from_config_str(src).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::WrongNumberOfColumnsInRow {
+                row_index: 1,
+                provided: 1,
+                required: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_configs() {
+        let inputs = [
+            ("kind = \"tabular\"", "Missing \"column_types\""),
+            ("kind = \"tabulary\"", "Unknown table kind \"tabulary\""),
+            ("column_types = 3", "Unexpected value for \"column_types\""),
+            (
+                "column_types = \"l\"\nrows = [\n[\"a\"",
+                "Expected a \",\" or \"]\" in the array on line 3",
+            ),
+            ("column_types = \"l", "Unterminated string on line 1"),
+            (
+                "column_types = \"l\"\n\ncolumn_types = \"ll\"",
+                "Duplicate key \"column_types\" on line 3",
+            ),
+        ];
+
+        for &(src, msg) in &inputs {
+            let got = Table::from_config_str(src).unwrap_err();
+
+            assert_eq!(got, TableError::Config(msg.to_string()), "{}", src);
+        }
+    }
+
+    #[test]
+    fn tables_which_dont_fit_the_config_are_rejected() {
+        let mut captioned = Table::new(TableKind::Tabular, "textwidth", "ll");
+        captioned
+            .push_row_values(vec!["a", "b"])
+            .unwrap()
+            .caption("Letters");
+        let mut styled = Table::new(TableKind::Tabular, "textwidth", "l");
+        styled
+            .push_row(Row::from_cells(vec![Cell::colored_text(
+                "a".to_string(),
+                "red",
+            )]))
+            .unwrap();
+
+        for table in &[captioned, styled] {
+            match table.to_config_string() {
+                Err(TableError::Config(_)) => {}
+                other => panic!("Expected a config error, got {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::io::Read;
+
+use super::{Row, Table, TableError, TableKind};
+
+impl Table {
+    /// Create a table from CSV data.
+    ///
+    /// The first record becomes the table's header and every other record is
+    /// added as a normal row, with each record's width checked against
+    /// `column_types`. Cells can't contain newlines, so the line breaks in a
+    /// quoted field are replaced with spaces.
+    ///
+    /// > **Note:** This requires the `csv` feature.
+    pub fn from_csv_reader<R: Read>(
+        kind: TableKind,
+        mut reader: R,
+        column_types: String,
+    ) -> Result<Table, TableError> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|e| TableError::Csv(e.to_string()))?;
+
+        let mut table = Table::new(kind, "textwidth", &column_types);
+
+        for (i, record) in parse_records(&text)?.into_iter().enumerate() {
+            let mut row: Row = record.iter().map(|field| join_lines(field)).collect();
+            if i == 0 {
+                row.is_header = true;
+                row.is_first_header = true;
+            }
+
+            table.push_row(row).map_err(|e| e.in_row(i))?;
+        }
+
+        Ok(table)
+    }
+}
+
+/// Split CSV text into records, following the quoting rules from RFC 4180.
+/// Blank lines are skipped.
+fn parse_records(text: &str) -> Result<Vec<Vec<String>>, TableError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(field.split_off(0)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => end_record(&mut records, &mut record, &mut field),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(TableError::Csv("Unterminated quoted field".to_string()));
+    }
+    end_record(&mut records, &mut record, &mut field);
+
+    Ok(records)
+}
+
+/// Join the lines of a quoted field with spaces, skipping blank lines.
+fn join_lines(field: &str) -> String {
+    field
+        .split(['\r', '\n'])
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn end_record(records: &mut Vec<Vec<String>>, record: &mut Vec<String>, field: &mut String) {
+    if record.is_empty() && field.is_empty() {
+        return;
+    }
+
+    record.push(field.split_off(0));
+    records.push(record.split_off(0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_csv_with_header() {
+        let src = "Name,Age,City\nAlice,42,Perth\n\"Bob, Jr.\",7,\"The \"\"Big\"\" Smoke\"\n";
+
+        let table =
+            Table::from_csv_reader(TableKind::Tabular, src.as_bytes(), "lrl".to_string()).unwrap();
+
+        assert_eq!(table.rows.len(), 3);
+        assert!(table.rows[0].is_header);
+        assert!(table.rows[0].is_first_header);
+        assert!(!table.rows[1].is_header);
+        assert_eq!(table.rows[2].cells[0].value, "Bob, Jr.");
+        assert_eq!(table.rows[2].cells[2].value, "The \"Big\" Smoke");
+    }
+
+    #[test]
+    fn line_breaks_in_quoted_fields_become_spaces() {
+        let src = "\"a\r\n\nb\",c\n";
+
+        let table =
+            Table::from_csv_reader(TableKind::Tabular, src.as_bytes(), "ll".to_string()).unwrap();
+
+        assert_eq!(table.rows[0].cells[0].value, "a b");
+        assert!(!table.to_string().contains("\n\n"));
+    }
+
+    #[test]
+    fn csv_records_with_wrong_width() {
+        let src = "a,b,c\r\n1,2,3\r\n4,5\r\n";
+
+        let got = Table::from_csv_reader(TableKind::Tabular, src.as_bytes(), "lll".to_string())
+            .unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::WrongNumberOfColumnsInRow {
+                row_index: 2,
+                provided: 2,
+                required: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn unterminated_quotes_are_an_error() {
+        let got = parse_records("a,\"b\n").unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::Csv("Unterminated quoted field".to_string())
+        );
+    }
+
+    #[test]
+    fn repeat_the_csv_header_on_every_long_table_page() {
+        let should_be = r"\begin{longtable}{lr}
+Name & Age\\
+\endfirsthead
+Name & Age\\
+\endhead
+Alice & 42\\
+\end{longtable}";
+        let src = "Name,Age\nAlice,42\n";
+
+        let table =
+            Table::from_csv_reader(TableKind::LongTable, src.as_bytes(), "lr".to_string()).unwrap();
+
+        assert_eq!(table.to_string(), should_be);
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use super::{Row, Table};
+
+impl Table {
+    /// Remove body rows which have the same cell values as the row directly
+    /// above them, keeping the first of each run.
+    ///
+    /// Header rows are never removed and rows on either side of a header
+    /// aren't considered consecutive. The same goes for rows with a
+    /// `\multirow` cell and the rows underneath it, since removing them
+    /// would break up the cell. Only the cell values are compared, so rows
+    /// which only differ in their formatting count as duplicates.
+    pub fn deduplicate_rows(&mut self) {
+        let mut previous: Option<Vec<String>> = None;
+        let mut spanned = spanned_rows(&self.rows).into_iter();
+
+        self.rows.retain(|row| {
+            if spanned.next().unwrap_or(false) || row.is_header {
+                previous = None;
+                return true;
+            }
+
+            let values = values(row);
+            let is_duplicate = previous.as_ref() == Some(&values);
+            previous = Some(values);
+            !is_duplicate
+        });
+    }
+
+    /// Remove every body row which has the same cell values as an earlier
+    /// body row, even when they aren't next to each other.
+    ///
+    /// Like `deduplicate_rows()`, header rows and rows under a `\multirow`
+    /// cell are never removed and only the cell values are compared.
+    pub fn dedup_all_rows(&mut self) {
+        let mut seen = HashSet::new();
+        let mut spanned = spanned_rows(&self.rows).into_iter();
+
+        self.rows.retain(|row| {
+            let spanned = spanned.next().unwrap_or(false);
+            spanned || row.is_header || seen.insert(values(row))
+        });
+    }
+}
+
+/// Find the rows which contain a `\multirow` cell or are covered by one.
+fn spanned_rows(rows: &[Row]) -> Vec<bool> {
+    let mut spanned = vec![false; rows.len()];
+
+    for (i, row) in rows.iter().enumerate() {
+        for &(span, _) in row.iter().filter_map(|cell| cell.multirow.as_ref()) {
+            let end = (i + span.max(1)).min(rows.len());
+            for flag in &mut spanned[i..end] {
+                *flag = true;
+            }
+        }
+    }
+
+    spanned
+}
+
+fn values(row: &Row) -> Vec<String> {
+    row.iter().map(|cell| cell.value.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tables::Cell;
+
+    fn table_with_rows(rows: &[&str]) -> Table {
+        let mut table = Table::tabular("l");
+        for value in rows {
+            table.push_row_values(vec![*value]).unwrap();
+        }
+        table
+    }
+
+    fn first_column(table: &Table) -> Vec<&str> {
+        table
+            .iter()
+            .map(|row| row.cells[0].value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn remove_consecutive_duplicates() {
+        let mut table = table_with_rows(&["a", "a", "b", "a", "a", "a"]);
+
+        table.deduplicate_rows();
+
+        assert_eq!(first_column(&table), vec!["a", "b", "a"]);
+    }
+
+    #[test]
+    fn remove_all_duplicates() {
+        let mut table = table_with_rows(&["a", "a", "b", "a", "c", "b"]);
+
+        table.dedup_all_rows();
+
+        assert_eq!(first_column(&table), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn headers_are_never_removed() {
+        let mut table = table_with_rows(&["h", "h", "a", "h", "a"]);
+        table.rows[0].is_header = true;
+        table.rows[1].is_header = true;
+        table.rows[3].is_header = true;
+        let mut dedup_all = table.clone();
+
+        table.deduplicate_rows();
+        dedup_all.dedup_all_rows();
+
+        assert_eq!(first_column(&table), vec!["h", "h", "a", "h", "a"]);
+        assert_eq!(first_column(&dedup_all), vec!["h", "h", "a", "h"]);
+    }
+
+    #[test]
+    fn rows_under_a_multirow_are_kept() {
+        let mut table = table_with_rows(&["a", "a", "", "", "b", "b"]);
+        table.rows[1].cells[0] = Cell::multirow(3, "*", "a".to_string());
+        let mut dedup_all = table.clone();
+
+        table.deduplicate_rows();
+        dedup_all.dedup_all_rows();
+
+        assert_eq!(first_column(&table), vec!["a", "a", "", "", "b"]);
+        assert_eq!(first_column(&dedup_all), vec!["a", "a", "", "", "b"]);
+    }
+}
@@ -0,0 +1,248 @@
+use super::{Cell, Content, Row, Table};
+
+/// Room for everything with a fixed size (e.g. `\begin{table}`, the booktabs
+/// rules and the long table header markers).
+const TABLE_OVERHEAD: usize = 256;
+/// Room for a row's terminator, newlines and the rule and space after it.
+const ROW_OVERHEAD: usize = 64;
+/// Room for the commands wrapped around a cell (e.g. `\multicolumn{...}{...}`
+/// and the text styles), not counting their arguments.
+const CELL_OVERHEAD: usize = 96;
+
+impl Table {
+    /// Estimate how many bytes the rendered table takes up, e.g. to
+    /// pre-allocate a buffer.
+    ///
+    /// The estimate is meant to be an upper bound and is usually a little
+    /// too big, but it is never too small for ASCII content.
+    pub fn estimate_render_len(&self) -> usize {
+        let settings = [
+            &self.caption,
+            &self.caption_continued,
+            &self.label,
+            &self.placement,
+            &self.row_stretch,
+            &self.env_options,
+        ];
+        let settings_len: usize = settings
+            .iter()
+            .filter_map(|setting| setting.as_ref())
+            .map(|text| escaped_len(text))
+            .sum();
+        let weights_len: usize = self
+            .column_weights
+            .iter()
+            .map(|(_, weight)| weight.len() + 32)
+            .sum();
+        let rule_weights_len: usize = self
+            .rule_weights
+            .iter()
+            .map(|weight| weight.to_string().len())
+            .sum();
+        let row_colors_len = self.row_colors.as_ref().map_or(0, |(start, odd, even)| {
+            start.to_string().len() + odd.len() + even.len()
+        });
+        let width_len = self.table_width.len()
+            + self
+                .width
+                .as_ref()
+                .map_or(0, |width| width.to_string().len());
+        let rows_len: usize = self.rows.iter().map(Row::estimate_len).sum();
+        let padding_len = if self.pretty {
+            self.rows.len() * self.pretty_widths().iter().sum::<usize>()
+        } else {
+            0
+        };
+
+        TABLE_OVERHEAD
+            + 2 * self.kind.environment_name().len()
+            + width_len
+            + self.column_types.len()
+            + self.vertical_rules.len()
+            + weights_len
+            + rule_weights_len
+            + row_colors_len
+            + settings_len
+            + rows_len
+            + padding_len
+    }
+}
+
+impl Row {
+    fn estimate_len(&self) -> usize {
+        let separators = 3 * self.cells.len();
+        let color = self.color.as_ref().map_or(0, |color| color.len());
+        let space = self.space_after.as_ref().map_or(0, |space| space.len());
+        let cells: usize = self.cells.iter().map(Cell::estimate_len).sum();
+
+        ROW_OVERHEAD + color + space + separators + cells
+    }
+}
+
+impl Cell {
+    fn estimate_len(&self) -> usize {
+        let value = match self.content {
+            Content::Text if self.raw => self.value.len(),
+            Content::Text => escaped_len(&self.value),
+            _ => {
+                // the other kinds are small, so they are simply rendered
+                let mut rendered = String::new();
+                self.fmt_content(&mut rendered)
+                    .map_or(0, |_| rendered.len())
+            }
+        };
+        let arguments = [
+            self.multicolumn.as_ref().map(|(_, spec)| spec),
+            self.multirow.as_ref().map(|(_, width)| width),
+            self.paragraph.as_ref().map(|(_, width)| width),
+            self.background.as_ref(),
+            self.text_color.as_ref(),
+        ];
+        let arguments_len: usize = arguments
+            .iter()
+            .filter_map(|argument| argument.map(|text| text.len()))
+            .sum();
+        let link = self.link.as_ref().map_or(0, |url| escaped_len(url));
+        let nested = self
+            .nested
+            .as_ref()
+            .map_or(0, |table| table.estimate_render_len());
+
+        CELL_OVERHEAD + value + arguments_len + link + nested
+    }
+}
+
+/// The length of some text once it has been passed through `escape()`.
+fn escaped_len(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => 2,
+            '~' => r"\textasciitilde{}".len(),
+            '^' => r"\textasciicircum{}".len(),
+            '\\' => r"\textbackslash{}".len(),
+            _ => c.len_utf8(),
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tables::{
+        BorderStyle, CellStyle, Rule, RuleWeight, Segment, TableKind, TableWidth, Trim, VAlign,
+    };
+
+    fn assert_upper_bound(table: &Table) {
+        let rendered = table.to_string();
+
+        assert!(
+            table.estimate_render_len() >= rendered.len(),
+            "estimated {} bytes but rendered {}:\n{}",
+            table.estimate_render_len(),
+            rendered.len(),
+            rendered
+        );
+    }
+
+    #[test]
+    fn estimate_empty_table() {
+        assert_upper_bound(&Table::tabular(""));
+    }
+
+    #[test]
+    fn estimate_simple_table() {
+        let mut table = Table::tabularx("lX");
+        table
+            .push_row_values(vec!["Name", "Description"])
+            .unwrap()
+            .push_row_values(vec!["a", "b"])
+            .unwrap();
+
+        assert_upper_bound(&table);
+    }
+
+    #[test]
+    fn estimate_table_with_every_feature() {
+        let mut table = Table::xltabular("|lXX@{}S|");
+        let style = CellStyle {
+            bold: true,
+            italic: true,
+            underline: true,
+        };
+        let mut header = Row::from_cells(vec![
+            Cell::formatted(r"\\\\\\".to_string(), style),
+            Cell::multicolumn(2, 'c', "~^~^".to_string()),
+            Cell::with_background("%%%".to_string(), "red!50"),
+        ]);
+        header.is_header = true;
+        header.is_first_header = true;
+        header.rule_after = Some(Rule::CMidRule(2, 4, Trim::Both));
+        header.space_after("0.5em");
+        let body = Row::from_cells(vec![
+            Cell::multirow(2, "3cm", "&&".to_string()),
+            Cell::paragraph("x".to_string(), "2cm", VAlign::Middle),
+            Cell::code("a--b".to_string()),
+            Cell::numeric(-12345.125),
+        ]);
+        let links = Row::from_cells(vec![
+            Cell::href("https://example.com/#%".to_string(), "$".to_string()),
+            Cell::math("x^2 + $".to_string()),
+            Cell::from_segments(vec![Segment::Text("&".to_string())]),
+            Cell::colored_text("y".to_string(), "blue"),
+        ]);
+        table
+            .push_row(header)
+            .unwrap()
+            .push_row(body)
+            .unwrap()
+            .push_row(links)
+            .unwrap();
+        table
+            .caption("50% off & more")
+            .caption_continued("continued ~")
+            .label("tab:everything")
+            .zebra("gray!10", "white")
+            .row_colors(1, "red!5", "blue!5")
+            .env_options("c")
+            .pretty(true)
+            .row_stretch(1.25)
+            .set_width(TableWidth::Absolute("0.8\\linewidth".to_string()))
+            .with_border_style(BorderStyle::Grid)
+            .booktabs_rule_weights(
+                RuleWeight::Heavy,
+                RuleWeight::Light,
+                RuleWeight::Custom("0.5pt".to_string()),
+            );
+        table.set_column_weight(1, 1.5).unwrap();
+        table.set_column_weight(2, 0.5).unwrap();
+        table.add_vertical_rule(4).unwrap();
+
+        assert_upper_bound(&table);
+    }
+
+    #[test]
+    fn estimate_floating_table() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+        table.push_row_values(vec!["\\", "\\"]).unwrap();
+        table
+            .caption("^^^")
+            .label("tab:float")
+            .placement("htbp")
+            .centered(true)
+            .use_booktabs(true);
+
+        assert_upper_bound(&table);
+    }
+
+    #[test]
+    fn estimate_nested_table() {
+        let mut inner = Table::tabular("l");
+        inner.push_row_values(vec!["inner"]).unwrap();
+        let mut outer = Table::tabular("l");
+        outer
+            .push_row(Row::from_cells(vec![Cell::nested(inner)]))
+            .unwrap();
+
+        assert_upper_bound(&outer);
+    }
+}
@@ -0,0 +1,244 @@
+use super::{columns, Cell, Row, Table};
+
+impl Table {
+    /// Render the table as a HTML `<table>`.
+    ///
+    /// Header rows go in the `<thead>` and use `<th>` cells, while every
+    /// other row goes in the `<tbody>`. Cells in `l`, `c` and `r` columns are
+    /// aligned accordingly, and cells spanning several columns or rows are
+    /// given a `colspan` or `rowspan`. The empty cells underneath a `rowspan`
+    /// are left out, because HTML doesn't need them. Cells with a `link`
+    /// become `<a href="...">` links and nested tables are rendered as nested
+    /// `<table>`s.
+    pub fn to_html(&self) -> String {
+        let letters = columns::column_letters(&self.column_types);
+        let header: Vec<&Row> = self.header_rows().collect();
+        let body: Vec<&Row> = self.body_rows().collect();
+
+        let mut rendered = String::from("<table>\n");
+
+        if !header.is_empty() {
+            rendered.push_str("<thead>\n");
+            push_rows(&mut rendered, &header, "th", &letters);
+            rendered.push_str("</thead>\n");
+        }
+
+        if !body.is_empty() {
+            rendered.push_str("<tbody>\n");
+            push_rows(&mut rendered, &body, "td", &letters);
+            rendered.push_str("</tbody>\n");
+        }
+
+        rendered.push_str("</table>\n");
+        rendered
+    }
+}
+
+fn push_rows(rendered: &mut String, rows: &[&Row], tag: &str, letters: &[char]) {
+    // how many more rows each column is covered by a rowspan for
+    let mut covered: Vec<usize> = vec![0; letters.len()];
+
+    for row in rows {
+        let skipped: Vec<bool> = covered.iter().map(|&rows| rows > 0).collect();
+        for rows in &mut covered {
+            *rows = rows.saturating_sub(1);
+        }
+
+        push_row(rendered, row, tag, letters, &skipped, &mut covered);
+    }
+}
+
+fn push_row(
+    rendered: &mut String,
+    row: &Row,
+    tag: &str,
+    letters: &[char],
+    skipped: &[bool],
+    covered: &mut [usize],
+) {
+    rendered.push_str("<tr>");
+    let mut column = 0;
+
+    for cell in row.iter() {
+        let span = cell.column_span();
+        if skipped.get(column) == Some(&true) {
+            column += span;
+            continue;
+        }
+
+        rendered.push('<');
+        rendered.push_str(tag);
+
+        if cell.column_span() > 1 {
+            rendered.push_str(&format!(" colspan=\"{}\"", cell.column_span()));
+        }
+        if let Some((rows, _)) = cell.multirow {
+            rendered.push_str(&format!(" rowspan=\"{}\"", rows));
+            for covered in covered.iter_mut().skip(column).take(span) {
+                *covered = rows.saturating_sub(1);
+            }
+        }
+
+        let letter = match cell.multicolumn {
+            Some((_, ref spec)) => columns::column_letters(spec).first().cloned(),
+            None => letters.get(column).cloned(),
+        };
+        match letter {
+            Some('l') => rendered.push_str(" style=\"text-align: left\""),
+            Some('c') => rendered.push_str(" style=\"text-align: center\""),
+            Some('r') => rendered.push_str(" style=\"text-align: right\""),
+            _ => {}
+        }
+
+        rendered.push('>');
+        rendered.push_str(&contents(cell));
+        rendered.push_str("</");
+        rendered.push_str(tag);
+        rendered.push('>');
+
+        column += span;
+    }
+
+    rendered.push_str("</tr>\n");
+}
+
+/// The HTML inside a cell's tag.
+fn contents(cell: &Cell) -> String {
+    if let Some(ref nested) = cell.nested {
+        return format!("\n{}", nested.to_html());
+    }
+
+    match cell.link {
+        Some(ref url) => format!("<a href=\"{}\">{}</a>", escape(url), escape(&cell.value)),
+        None => escape(&cell.value),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TableKind;
+
+    #[test]
+    fn html_header_and_body() {
+        let should_be = r#"<table>
+<thead>
+<tr><th style="text-align: left">Name</th><th style="text-align: right">Score</th></tr>
+</thead>
+<tbody>
+<tr><td style="text-align: left">Alice &amp; Bob</td><td style="text-align: right">&lt;10&gt;</td></tr>
+</tbody>
+</table>
+"#;
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+        table
+            .push_row_values(vec!["Name", "Score"])
+            .unwrap()
+            .push_row_values(vec!["Alice & Bob", "<10>"])
+            .unwrap();
+        table.rows[0].is_header = true;
+
+        assert_eq!(table.to_html(), should_be);
+    }
+
+    #[test]
+    fn html_without_header() {
+        let should_be = r#"<table>
+<tbody>
+<tr><td>&quot;quoted&quot;</td></tr>
+</tbody>
+</table>
+"#;
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "p{2cm}");
+        table.push_row_values(vec!["\"quoted\""]).unwrap();
+
+        assert_eq!(table.to_html(), should_be);
+    }
+
+    #[test]
+    fn html_multicolumn_cells_use_colspan() {
+        let should_be = r#"<table>
+<tbody>
+<tr><td colspan="2" style="text-align: center">Title</td><td style="text-align: right">x</td></tr>
+</tbody>
+</table>
+"#;
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "llr");
+        let mut row = Row::from_cells(vec![Cell::multicolumn(2, 'c', "Title".to_string())]);
+        row.push_cell("x");
+        table.push_row(row).unwrap();
+
+        assert_eq!(table.to_html(), should_be);
+    }
+
+    #[test]
+    fn html_rowspan_skips_the_covered_cells() {
+        let should_be = r#"<table>
+<tbody>
+<tr><td rowspan="2">Group</td><td>a</td></tr>
+<tr><td>b</td></tr>
+<tr><td>c</td><td>d</td></tr>
+</tbody>
+</table>
+"#;
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "XX");
+        table
+            .push_row(Row::from_cells(vec![
+                Cell::multirow(2, "*", "Group".to_string()),
+                "a".into(),
+            ]))
+            .unwrap()
+            .push_row_values(vec!["", "b"])
+            .unwrap()
+            .push_row_values(vec!["c", "d"])
+            .unwrap();
+
+        assert_eq!(table.to_html(), should_be);
+    }
+
+    #[test]
+    fn html_links_and_nested_tables() {
+        let should_be = r#"<table>
+<tbody>
+<tr><td><a href="https://example.com/?a=1&amp;b=2">Docs</a></td><td>
+<table>
+<tbody>
+<tr><td>inner</td></tr>
+</tbody>
+</table>
+</td></tr>
+</tbody>
+</table>
+"#;
+        let mut inner = Table::new(TableKind::Tabular, "textwidth", "X");
+        inner.push_row_values(vec!["inner"]).unwrap();
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "XX");
+        table
+            .push_row(Row::from_cells(vec![
+                Cell::href(
+                    "https://example.com/?a=1&b=2".to_string(),
+                    "Docs".to_string(),
+                ),
+                Cell::nested(inner),
+            ]))
+            .unwrap();
+
+        assert_eq!(table.to_html(), should_be);
+    }
+}
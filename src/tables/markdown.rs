@@ -0,0 +1,123 @@
+use super::{columns, Cell, Row, Table};
+
+impl Table {
+    /// Render the table as a GitHub-flavoured Markdown table.
+    ///
+    /// The first header row (or the first row, if there are no header rows)
+    /// becomes the Markdown table's header, and `l`, `c` and `r` columns are
+    /// aligned accordingly. Cells are written as plain text, so cells which
+    /// span several columns or rows only show their value.
+    pub fn to_markdown(&self) -> String {
+        let header = match self.rows.iter().position(|row| row.is_header) {
+            Some(i) => i,
+            None if self.rows.is_empty() => return String::new(),
+            None => 0,
+        };
+
+        let mut rendered = String::new();
+        push_row(&mut rendered, &self.rows[header]);
+
+        rendered.push('|');
+        for letter in columns::column_letters(&self.column_types) {
+            let separator = match letter {
+                'l' => " :--- |",
+                'c' => " :---: |",
+                'r' => " ---: |",
+                _ => " --- |",
+            };
+            rendered.push_str(separator);
+        }
+        rendered.push('\n');
+
+        for (i, row) in self.rows.iter().enumerate() {
+            if i != header {
+                push_row(&mut rendered, row);
+            }
+        }
+
+        rendered
+    }
+}
+
+fn push_row(rendered: &mut String, row: &Row) {
+    rendered.push('|');
+
+    for cell in row.iter() {
+        rendered.push(' ');
+        rendered.push_str(&escape(cell));
+        rendered.push_str(" |");
+
+        // pad out the rest of a multicolumn cell so the columns line up
+        for _ in 1..cell.column_span() {
+            rendered.push_str("  |");
+        }
+    }
+
+    rendered.push('\n');
+}
+
+fn escape(cell: &Cell) -> String {
+    cell.value.replace('|', r"\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TableKind;
+
+    #[test]
+    fn markdown_alignments() {
+        let should_be = "| Left | Centre | Right | Other |
+| :--- | :---: | ---: | --- |
+| a | b | c | d |
+";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lcrp{2cm}");
+        table
+            .push_row_values(vec!["Left", "Centre", "Right", "Other"])
+            .unwrap()
+            .push_row_values(vec!["a", "b", "c", "d"])
+            .unwrap();
+
+        assert_eq!(table.to_markdown(), should_be);
+    }
+
+    #[test]
+    fn markdown_uses_the_header_row() {
+        let should_be = "| Name | Value |
+| :--- | ---: |
+| first | 1 |
+| second | 2 |
+";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+        table
+            .push_row_values(vec!["first", "1"])
+            .unwrap()
+            .push_row_values(vec!["Name", "Value"])
+            .unwrap()
+            .push_row_values(vec!["second", "2"])
+            .unwrap();
+        table.rows[1].is_header = true;
+
+        assert_eq!(table.to_markdown(), should_be);
+    }
+
+    #[test]
+    fn markdown_escapes_pipes() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "l");
+        table.push_row_values(vec!["a | b"]).unwrap();
+
+        assert_eq!(table.to_markdown(), "| a \\| b |\n| :--- |\n");
+    }
+
+    #[test]
+    fn markdown_multicolumn_falls_back_to_plain_text() {
+        let should_be = "| Title |  |
+| :---: | :---: |
+";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "cc");
+        let row = Row::from_cells(vec![Cell::multicolumn(2, 'c', "Title".to_string())]);
+        table.push_row(row).unwrap();
+
+        assert_eq!(table.to_markdown(), should_be);
+    }
+}
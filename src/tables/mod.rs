@@ -0,0 +1,3758 @@
+//! Tables and the environments used to typeset them.
+
+mod builder;
+mod cell;
+mod columns;
+#[cfg(feature = "table-config")]
+mod config;
+#[cfg(feature = "csv")]
+mod csv;
+mod dedup;
+mod estimate;
+mod html;
+mod markdown;
+mod plain_text;
+mod row;
+mod sort;
+mod transpose;
+
+pub use self::builder::TableBuilder;
+pub use self::cell::{Cell, CellStyle, Content, Segment, Symbol, VAlign};
+pub use self::columns::Column;
+pub use self::plain_text::TextRenderOptions;
+pub use self::row::{Row, Rule, Trim};
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::ops::{Index, IndexMut};
+use std::slice::Iter;
+use std::vec::IntoIter;
+
+use document::{Document, DocumentClass};
+use Error;
+
+/// Which environment should be used to typeset a `Table`?
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TableKind {
+    /// A plain `tabular` environment.
+    Tabular,
+    /// A `tabularx` environment, which stretches `X` columns to fill a fixed
+    /// width.
+    Tabularx,
+    /// A `longtable` environment, which can be broken across pages.
+    LongTable,
+    /// An `xltabular` environment, a `tabularx` which can be broken across
+    /// pages.
+    XLTabular,
+}
+
+impl TableKind {
+    /// Get the `TableKind`'s environment name.
+    pub fn environment_name(&self) -> &str {
+        match *self {
+            TableKind::Tabular => "tabular",
+            TableKind::Tabularx => "tabularx",
+            TableKind::LongTable => "longtable",
+            TableKind::XLTabular => "xltabular",
+        }
+    }
+
+    /// Does the environment take the table's width as an argument?
+    pub fn requires_width(&self) -> bool {
+        match *self {
+            TableKind::Tabularx | TableKind::XLTabular => true,
+            TableKind::Tabular | TableKind::LongTable => false,
+        }
+    }
+
+    /// Can the table be broken across several pages?
+    pub fn is_long(&self) -> bool {
+        match *self {
+            TableKind::LongTable | TableKind::XLTabular => true,
+            TableKind::Tabular | TableKind::Tabularx => false,
+        }
+    }
+
+    /// The packages needed for this environment, in the order they need to
+    /// be loaded.
+    ///
+    /// `xltabular` builds on `tabularx` and `longtable`, so they are loaded
+    /// before it.
+    pub fn packages(&self) -> &'static [&'static str] {
+        match *self {
+            TableKind::Tabular => &[],
+            TableKind::Tabularx => &["tabularx"],
+            TableKind::LongTable => &["longtable"],
+            TableKind::XLTabular => &["tabularx", "longtable", "xltabular"],
+        }
+    }
+}
+
+/// The width of a `Tabularx` or `XLTabular` table.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TableWidth {
+    /// The width of the page's text (`\textwidth`).
+    TextWidth,
+    /// The width of the current line (`\linewidth`), which is narrower than
+    /// `\textwidth` inside lists and similar environments.
+    LineWidth,
+    /// The width of the current column in multi-column documents
+    /// (`\columnwidth`).
+    ColumnWidth,
+    /// Any other length, written as-is (e.g. `12cm` or `0.8\textwidth`).
+    Absolute(String),
+}
+
+impl Display for TableWidth {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            TableWidth::TextWidth => write!(f, r"\textwidth"),
+            TableWidth::LineWidth => write!(f, r"\linewidth"),
+            TableWidth::ColumnWidth => write!(f, r"\columnwidth"),
+            TableWidth::Absolute(ref length) => write!(f, "{}", length),
+        }
+    }
+}
+
+/// The common ways of drawing rules in a table, for use with
+/// `Table::with_border_style()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BorderStyle {
+    /// No rules at all.
+    None,
+    /// `booktabs` rules above and below the table and under the header (see
+    /// `Table::use_booktabs()`).
+    Booktabs,
+    /// An `\hline` above and below every row (see `Table::grid()`).
+    Grid,
+    /// A single `\hline` under the header rows.
+    HeaderOnly,
+}
+
+/// The thickness of a `booktabs` rule, for use with
+/// `Table::booktabs_rule_weights()`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum RuleWeight {
+    /// The rule's normal thickness.
+    #[default]
+    Default,
+    /// As thick as a `\toprule` or `\bottomrule` (`\heavyrulewidth`).
+    Heavy,
+    /// As thin as a `\midrule` (`\lightrulewidth`).
+    Light,
+    /// Some other thickness (e.g. `0.12em`).
+    Custom(String),
+}
+
+impl Display for RuleWeight {
+    /// Write the rule's optional argument, which is empty for the default
+    /// thickness.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            RuleWeight::Default => Ok(()),
+            RuleWeight::Heavy => write!(f, r"[\heavyrulewidth]"),
+            RuleWeight::Light => write!(f, r"[\lightrulewidth]"),
+            RuleWeight::Custom(ref thickness) => write!(f, "[{}]", thickness),
+        }
+    }
+}
+
+/// Where a table's caption goes within its `table` float.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum CaptionPosition {
+    /// Put the caption before the rows.
+    Above,
+    /// Put the caption after the rows.
+    #[default]
+    Below,
+}
+
+/// The errors which can occur when building a `Table`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TableError {
+    /// A row had the wrong number of columns (provided, required).
+    ///
+    /// Methods which add several rows at once report
+    /// `WrongNumberOfColumnsInRow` instead, so you know which row was wrong.
+    WrongNumberOfColumns(usize, usize),
+    /// One of the rows had the wrong number of columns.
+    WrongNumberOfColumnsInRow {
+        /// The index of the offending row.
+        row_index: usize,
+        /// The number of columns the row had.
+        provided: usize,
+        /// The number of columns the table requires.
+        required: usize,
+    },
+    /// The column specification contained an unknown column type.
+    InvalidColumnType(char),
+    /// The column with this index doesn't exist or isn't an `X` column.
+    NotAnXColumn(usize),
+    /// A cell's value contained a newline.
+    CellContainsNewline,
+    /// A `\multicolumn` cell spans more columns than are left in its row.
+    SpanTooWide {
+        /// The column the cell starts in.
+        column_index: usize,
+        /// The number of columns the cell spans.
+        span: usize,
+        /// The number of columns left in the row.
+        remaining: usize,
+    },
+    /// Tried to insert a row past the end of the table.
+    RowIndexOutOfBounds {
+        /// The index the row was meant to go at.
+        index: usize,
+        /// The number of rows in the table.
+        row_count: usize,
+    },
+    /// The table contains a cell which spans several columns or rows, so it
+    /// can't be rearranged.
+    SpanningCell {
+        /// The index of the row containing the cell.
+        row_index: usize,
+        /// The index of the cell within its row.
+        column_index: usize,
+    },
+    /// A `\cline` or `\cmidrule` goes across columns which aren't in the
+    /// table.
+    RuleOutOfRange {
+        /// The first column the rule goes across.
+        start: usize,
+        /// The last column the rule goes across.
+        end: usize,
+        /// The number of columns in the table.
+        column_count: usize,
+    },
+    /// Tried to refer to a column which isn't in the table.
+    ColumnIndexOutOfBounds {
+        /// The index of the column.
+        index: usize,
+        /// The number of columns in the table.
+        column_count: usize,
+    },
+    /// Tried to merge two tables with different column specifications.
+    IncompatibleTables {
+        /// The column specification of the table being merged into.
+        column_types: String,
+        /// The column specification of the table being merged in.
+        other_column_types: String,
+    },
+    /// Tried to split a table into tables too narrow to fit the key columns
+    /// and at least one other column.
+    TooManyKeyColumns {
+        /// The number of columns repeated in every table.
+        key_columns: usize,
+        /// The most columns each table may have.
+        max_columns: usize,
+    },
+    /// The CSV data couldn't be read.
+    #[cfg(feature = "csv")]
+    Csv(String),
+    /// The TOML config couldn't be read, or the table can't be written as
+    /// one.
+    #[cfg(feature = "table-config")]
+    Config(String),
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            TableError::WrongNumberOfColumns(provided, required) => write!(
+                f,
+                "The row has {} columns but the table requires {}",
+                provided, required
+            ),
+            TableError::WrongNumberOfColumnsInRow {
+                row_index,
+                provided,
+                required,
+            } => write!(
+                f,
+                "Row {} has {} columns but the table requires {}",
+                row_index, provided, required
+            ),
+            TableError::InvalidColumnType(c) => write!(f, "\"{}\" isn't a known column type", c),
+            TableError::NotAnXColumn(index) => write!(f, "Column {} isn't an X column", index),
+            TableError::CellContainsNewline => write!(f, "Cells can't contain newlines"),
+            TableError::SpanTooWide {
+                column_index,
+                span,
+                remaining,
+            } => write!(
+                f,
+                "The cell in column {} spans {} columns but only {} are left",
+                column_index, span, remaining
+            ),
+            TableError::RowIndexOutOfBounds { index, row_count } => write!(
+                f,
+                "Can't insert a row at index {} of a table with {} rows",
+                index, row_count
+            ),
+            TableError::SpanningCell {
+                row_index,
+                column_index,
+            } => write!(
+                f,
+                "Cell {} in row {} spans several columns or rows",
+                column_index, row_index
+            ),
+            TableError::RuleOutOfRange {
+                start,
+                end,
+                column_count,
+            } => write!(
+                f,
+                "Can't draw a rule across columns {}-{} of a table with {} columns",
+                start, end, column_count
+            ),
+            TableError::ColumnIndexOutOfBounds {
+                index,
+                column_count,
+            } => write!(
+                f,
+                "Column {} is out of bounds for a table with {} columns",
+                index, column_count
+            ),
+            TableError::IncompatibleTables {
+                ref column_types,
+                ref other_column_types,
+            } => write!(
+                f,
+                "Can't merge a table with columns \"{}\" into one with columns \"{}\"",
+                other_column_types, column_types
+            ),
+            TableError::TooManyKeyColumns {
+                key_columns,
+                max_columns,
+            } => write!(
+                f,
+                "Can't fit {} key columns and another column into tables with at most {} columns",
+                key_columns, max_columns
+            ),
+            #[cfg(feature = "csv")]
+            TableError::Csv(ref msg) => write!(f, "Unable to read the CSV data: {}", msg),
+            #[cfg(feature = "table-config")]
+            TableError::Config(ref msg) => write!(f, "Invalid table config: {}", msg),
+        }
+    }
+}
+
+impl TableError {
+    /// Attach the index of the offending row to a `WrongNumberOfColumns`
+    /// error.
+    fn in_row(self, row_index: usize) -> TableError {
+        match self {
+            TableError::WrongNumberOfColumns(provided, required) => {
+                TableError::WrongNumberOfColumnsInRow {
+                    row_index,
+                    provided,
+                    required,
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+impl error::Error for TableError {}
+
+/// A table of rows and columns.
+///
+/// # Examples
+///
+/// The typical workflow is to create an empty `Table` with the desired column
+/// specification and then add rows to it.
+///
+/// ```rust
+/// use latex::{Row, Table, TableKind};
+///
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+///
+/// let mut row = Row::new();
+/// row.push_cell("Name").push_cell("Age");
+/// table.push_row(row)?;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// # run().unwrap();
+/// # }
+/// ```
+///
+/// When rendered, you should get something like this:
+///
+/// ```tex
+/// \begin{tabular}{lr}
+/// Name & Age\\
+/// \end{tabular}
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Table {
+    /// The environment used to typeset this table.
+    pub kind: TableKind,
+    /// The width of the table without the leading backslash (e.g.
+    /// `textwidth`), only used by `Tabularx` and `XLTabular`.
+    ///
+    /// Prefer `set_width()`, which takes precedence over this field.
+    pub table_width: String,
+    /// The column specification (e.g. `lcr`).
+    ///
+    /// Prefer `set_column_types()` when changing this, so the table's column
+    /// count is kept up to date. Assigning to it directly leaves the old count
+    /// behind and rows are checked against the wrong number of columns.
+    pub column_types: String,
+    column_count: usize,
+    /// The rows in this table.
+    pub rows: Vec<Row>,
+    booktabs: bool,
+    caption: Option<String>,
+    caption_continued: Option<String>,
+    raw_captions: bool,
+    label: Option<String>,
+    placement: Option<String>,
+    centered: bool,
+    header_rule: bool,
+    // f32 settings are stored as text so the table can still be `Eq` and
+    // `Hash`
+    row_stretch: Option<String>,
+    width: Option<TableWidth>,
+    grid: bool,
+    caption_position: CaptionPosition,
+    column_weights: Vec<(usize, String)>,
+    vertical_rules: Vec<usize>,
+    rule_weights: [RuleWeight; 3],
+    env_options: Option<String>,
+    pretty: bool,
+    captionof: bool,
+    trailing_row_terminator: bool,
+    rotate: Option<i16>,
+    row_colors: Option<(usize, String, String)>,
+}
+
+impl Table {
+    /// Create an empty table.
+    ///
+    /// > **Note:** The column specification isn't checked, so a typo (e.g.
+    /// > `lXz`) will only be noticed when LaTeX tries to compile the
+    /// > document. Use `try_new()` if you want to catch this early.
+    pub fn new(kind: TableKind, table_width: &str, column_types: &str) -> Table {
+        Table {
+            kind,
+            table_width: table_width.to_string(),
+            column_types: column_types.to_string(),
+            column_count: columns::count_columns(column_types),
+            rows: Vec::new(),
+            booktabs: false,
+            caption: None,
+            caption_continued: None,
+            raw_captions: false,
+            label: None,
+            placement: None,
+            centered: false,
+            header_rule: false,
+            row_stretch: None,
+            width: None,
+            grid: false,
+            caption_position: CaptionPosition::default(),
+            column_weights: Vec::new(),
+            vertical_rules: Vec::new(),
+            rule_weights: Default::default(),
+            env_options: None,
+            pretty: false,
+            captionof: false,
+            trailing_row_terminator: true,
+            rotate: None,
+            row_colors: None,
+        }
+    }
+
+    /// Create an empty `tabular` table.
+    pub fn tabular(column_types: &str) -> Table {
+        Table::new(TableKind::Tabular, "textwidth", column_types)
+    }
+
+    /// Create an empty `tabularx` table which is as wide as the text.
+    pub fn tabularx(column_types: &str) -> Table {
+        Table::new(TableKind::Tabularx, "textwidth", column_types)
+    }
+
+    /// Create an empty `longtable` table.
+    pub fn longtable(column_types: &str) -> Table {
+        Table::new(TableKind::LongTable, "textwidth", column_types)
+    }
+
+    /// Create an empty `xltabular` table which is as wide as the text.
+    pub fn xltabular(column_types: &str) -> Table {
+        Table::new(TableKind::XLTabular, "textwidth", column_types)
+    }
+
+    /// Create an empty table from a structured list of columns, rather than
+    /// a column specification string.
+    ///
+    /// Separators and vertical rules don't count towards the table's number
+    /// of columns.
+    pub fn from_columns(kind: TableKind, table_width: &str, columns: Vec<Column>) -> Table {
+        Table::new(kind, table_width, &columns::spec_from_columns(&columns))
+    }
+
+    /// Create a table from a list of rows, where each row is a list of cell
+    /// values.
+    ///
+    /// Every row is checked against the column specification, with the first
+    /// row which has the wrong number of columns being reported.
+    pub fn try_from_rows(
+        kind: TableKind,
+        table_width: &str,
+        column_types: &str,
+        rows: Vec<Vec<String>>,
+    ) -> Result<Table, TableError> {
+        Table::from_records(kind, table_width, column_types, rows)
+    }
+
+    /// Create a table from anything which can be iterated over as records,
+    /// where each record is a list of cell values (e.g. an iterator of
+    /// structs mapped to their fields).
+    ///
+    /// Records are turned into rows one at a time, stopping at the first one
+    /// which has the wrong number of columns and reporting its index.
+    pub fn from_records<I, R>(
+        kind: TableKind,
+        table_width: &str,
+        column_types: &str,
+        records: I,
+    ) -> Result<Table, TableError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = String>,
+    {
+        let mut table = Table::new(kind, table_width, column_types);
+
+        for (i, values) in records.into_iter().enumerate() {
+            table.push_row_values(values).map_err(|e| e.in_row(i))?;
+        }
+
+        Ok(table)
+    }
+
+    /// Get a `TableBuilder` for creating a table in a single expression.
+    pub fn builder() -> TableBuilder {
+        TableBuilder::new()
+    }
+
+    /// Create an empty table, making sure each column type in the column
+    /// specification is one LaTeX knows about.
+    pub fn try_new(
+        kind: TableKind,
+        table_width: &str,
+        column_types: &str,
+    ) -> Result<Table, TableError> {
+        match columns::find_invalid_column(column_types) {
+            Some(c) => Err(TableError::InvalidColumnType(c)),
+            None => Ok(Table::new(kind, table_width, column_types)),
+        }
+    }
+
+    /// The number of columns each row must have.
+    ///
+    /// Sized paragraph columns like `p{3cm}` count as a single column, while
+    /// vertical rules and `@{...}` separators aren't counted at all.
+    ///
+    /// > **Note:** This assumes only ASCII characters are used as column
+    /// > types.
+    pub fn column_count(&self) -> usize {
+        self.column_count
+    }
+
+    /// The column specification (e.g. `lcr`).
+    pub fn column_types(&self) -> &str {
+        &self.column_types
+    }
+
+    /// Replace the column specification, recounting the table's columns.
+    ///
+    /// Any column weights and vertical rules added with `add_vertical_rule()`
+    /// are removed, because they may no longer point at the right columns.
+    /// Existing rows aren't checked against the new columns,
+    /// so call `validate()` afterwards if they might not fit.
+    pub fn set_column_types(&mut self, column_types: &str) -> &mut Self {
+        self.column_types = column_types.to_string();
+        self.column_count = columns::count_columns(column_types);
+        self.column_weights.clear();
+        self.vertical_rules.clear();
+        self
+    }
+
+    /// Get the type of the column with this index, ignoring separators and
+    /// vertical rules.
+    pub fn column_alignment(&self, index: usize) -> Option<Column> {
+        columns::parse_columns(&self.column_types)
+            .into_iter()
+            .filter(|column| !column.is_separator())
+            .nth(index)
+    }
+
+    /// Add a row to the end of the table, making sure it has the correct
+    /// number of columns.
+    #[must_use = "the row isn't added when it has the wrong number of columns"]
+    pub fn push_row(&mut self, row: Row) -> Result<&mut Table, TableError> {
+        self.check_row(&row)?;
+
+        self.rows.push(row);
+        Ok(self)
+    }
+
+    /// Add a row to the end of the table, padding it with `fill` cells if it
+    /// is too short (e.g. when importing ragged data).
+    ///
+    /// Rows with too many columns are still rejected.
+    #[must_use = "the row isn't added when it has too many columns"]
+    pub fn push_row_padded(&mut self, mut row: Row, fill: &str) -> Result<&mut Table, TableError> {
+        for _ in row.column_count()..self.column_count {
+            row.push(fill);
+        }
+
+        self.push_row(row)
+    }
+
+    /// Insert a row at `index`, shifting every row after it down, and making
+    /// sure it has the correct number of columns.
+    pub fn insert_row(&mut self, index: usize, row: Row) -> Result<(), TableError> {
+        if index > self.rows.len() {
+            return Err(TableError::RowIndexOutOfBounds {
+                index,
+                row_count: self.rows.len(),
+            });
+        }
+        self.check_row(&row)?;
+
+        self.rows.insert(index, row);
+        Ok(())
+    }
+
+    /// Remove the row at `index`, if there is one.
+    pub fn remove_row(&mut self, index: usize) -> Option<Row> {
+        if index < self.rows.len() {
+            Some(self.rows.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Add a row to the end of the table without checking its number of
+    /// columns.
+    ///
+    /// The caller guarantees the row has the same width as the table.
+    pub fn push_row_unchecked(&mut self, row: Row) -> &mut Table {
+        self.rows.push(row);
+        self
+    }
+
+    /// Add a row to the end of the table, panicking if it has the wrong
+    /// number of columns.
+    ///
+    /// This is mainly useful in tests and quick scripts.
+    pub fn push_row_or_panic(&mut self, row: Row) -> &mut Table {
+        let row_index = self.rows.len();
+
+        match self.push_row(row) {
+            Ok(table) => table,
+            Err(e) => panic!("Unable to add row {} to the table: {}", row_index, e),
+        }
+    }
+
+    /// Add a row made up of plain cells with the provided values.
+    ///
+    /// This is a shortcut for building up a `Row` and then passing it to
+    /// `push_row()`.
+    pub fn push_row_values<I, S>(&mut self, values: I) -> Result<&mut Table, TableError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let row: Row = values.into_iter().map(Into::into).collect();
+        self.push_row(row)
+    }
+
+    /// Set how wide a `Tabularx` or `XLTabular` table is. This is ignored by
+    /// the other kinds of table.
+    pub fn set_width(&mut self, width: TableWidth) -> &mut Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set whether the table should be drawn with `booktabs` rules.
+    ///
+    /// When enabled, a `\toprule` goes above the first row, a `\midrule`
+    /// separates the header rows from the body, and a `\bottomrule` goes
+    /// below the last row.
+    pub fn use_booktabs(&mut self, enabled: bool) -> &mut Self {
+        self.booktabs = enabled;
+        self
+    }
+
+    /// Set the thickness of the `\toprule`, `\midrule` and `\bottomrule`
+    /// drawn when `use_booktabs()` is enabled.
+    pub fn booktabs_rule_weights(
+        &mut self,
+        top: RuleWeight,
+        mid: RuleWeight,
+        bottom: RuleWeight,
+    ) -> &mut Self {
+        self.rule_weights = [top, mid, bottom];
+        self
+    }
+
+    /// Give the table a caption.
+    ///
+    /// Long tables are captioned inside their environment, while the other
+    /// kinds of table get wrapped in a `table` float with the caption after
+    /// the rows (see `caption_position()`).
+    ///
+    /// Special characters are escaped the same way as in cells. Use
+    /// `raw_captions()` if the caption contains LaTeX commands.
+    pub fn caption(&mut self, text: &str) -> &mut Self {
+        self.caption = Some(text.to_string());
+        self
+    }
+
+    /// Write the captions into the document as-is, without escaping them.
+    pub fn raw_captions(&mut self, raw: bool) -> &mut Self {
+        self.raw_captions = raw;
+        self
+    }
+
+    /// Give a long table an unnumbered caption (`\caption*`) which is
+    /// repeated at the top of every page after the first, e.g.
+    /// "Results (continued)".
+    ///
+    /// The numbered caption from `caption()` is only shown on the first
+    /// page. This is ignored for tables which aren't long tables.
+    pub fn caption_continued(&mut self, text: &str) -> &mut Self {
+        self.caption_continued = Some(text.to_string());
+        self
+    }
+
+    /// Give the table a label so it can be referenced later.
+    ///
+    /// Labels are identifiers rather than text and are never escaped, so the
+    /// same `id` can be passed to `\ref`.
+    pub fn label(&mut self, id: &str) -> &mut Self {
+        self.label = Some(id.to_string());
+        self
+    }
+
+    /// Set whether the caption goes above or below the rows of a `table`
+    /// float. The default is `CaptionPosition::Below`.
+    ///
+    /// Long tables always have their caption at the top of their
+    /// environment, so this is ignored for `LongTable` and `XLTabular`.
+    pub fn caption_position(&mut self, position: CaptionPosition) -> &mut Self {
+        self.caption_position = position;
+        self
+    }
+
+    /// Caption the table with `\captionof{table}{...}` from the `caption`
+    /// package instead of wrapping it in a `table` float, e.g. when it is
+    /// inside a `minipage`.
+    ///
+    /// The caption and label still go above or below the table (see
+    /// `caption_position()`), but `placement()` is ignored. Long tables are
+    /// captioned inside their environment, so this doesn't affect them.
+    pub fn use_captionof(&mut self, enabled: bool) -> &mut Self {
+        self.captionof = enabled;
+        self
+    }
+
+    /// Set the placement specifier (e.g. `htbp`) used for the `table` float.
+    ///
+    /// Long tables aren't floats, so this is ignored for `LongTable` and
+    /// `XLTabular`.
+    pub fn placement(&mut self, spec: &str) -> &mut Self {
+        self.placement = Some(spec.to_string());
+        self
+    }
+
+    /// Set the optional argument passed to the table's environment, e.g. `c`
+    /// to center a `longtable` (`\begin{longtable}[c]{...}`).
+    ///
+    /// This goes before the width and column specification, and isn't
+    /// checked, so make sure the environment accepts it.
+    pub fn env_options(&mut self, options: &str) -> &mut Self {
+        self.env_options = Some(options.to_string());
+        self
+    }
+
+    /// Set whether the table should be centered within its `table` float.
+    ///
+    /// Like `placement()`, this is ignored by long tables.
+    pub fn centered(&mut self, yes: bool) -> &mut Self {
+        self.centered = yes;
+        self
+    }
+
+    /// Choose which rules are drawn in the table, replacing any earlier
+    /// `use_booktabs()` or `grid()` settings.
+    ///
+    /// Rules added to individual rows with `rule_after` are still drawn.
+    pub fn with_border_style(&mut self, style: BorderStyle) -> &mut Self {
+        self.booktabs = style == BorderStyle::Booktabs;
+        self.grid = style == BorderStyle::Grid;
+        self.header_rule = style == BorderStyle::HeaderOnly;
+        self
+    }
+
+    /// Space the rows further apart (or closer together) by scaling
+    /// `\arraystretch`, e.g. `1.3` for rows a third taller than normal.
+    ///
+    /// The change is wrapped in a group so it only affects this table.
+    pub fn row_stretch(&mut self, factor: f32) -> &mut Self {
+        // Stored pre-formatted so the table doesn't hold a float
+        self.row_stretch = Some(factor.to_string());
+        self
+    }
+
+    /// Draw an `\hline` above the first row and underneath every row, so
+    /// the table looks like a grid.
+    ///
+    /// Rows with their own `rule_after` keep that rule instead.
+    pub fn grid(&mut self, enabled: bool) -> &mut Self {
+        self.grid = enabled;
+        self
+    }
+
+    /// Pad each cell so the `&`s and `\\`s line up in the rendered LaTeX,
+    /// making it easier to read and diff (e.g. when it is checked into
+    /// version control).
+    ///
+    /// This only changes the whitespace, so the typeset table looks the same.
+    /// Cells which span several columns aren't padded.
+    pub fn pretty(&mut self, enabled: bool) -> &mut Self {
+        self.pretty = enabled;
+        self
+    }
+
+    /// Set whether the last row should end with `\\` (the default), for
+    /// environments which don't like a row terminator before `\end{...}`.
+    ///
+    /// The terminator is always kept when there is a rule or some space
+    /// underneath the last row (e.g. a `\bottomrule`), because LaTeX needs
+    /// it there.
+    pub fn trailing_row_terminator(&mut self, enabled: bool) -> &mut Self {
+        self.trailing_row_terminator = enabled;
+        self
+    }
+
+    /// Rotate the table anticlockwise by some number of degrees, e.g. to
+    /// fit a wide table on its side.
+    ///
+    /// The angle is wrapped into `0..360`, so `-90` is the same as `270`
+    /// and a multiple of 360 turns the rotation off. A floating table rotated
+    /// by 90 degrees is put in a `sidewaystable` from the `rotating`
+    /// package, which fills a whole page in landscape. Any other angle, or a
+    /// table which isn't floated (e.g. a bare `tabular`), wraps the
+    /// environment in `\rotatebox{<angle>}{...}` from `graphicx`.
+    ///
+    /// Long tables break across pages, which can't be rotated, so this is
+    /// ignored for `LongTable` and `XLTabular`.
+    pub fn rotate(&mut self, degrees: i16) -> &mut Self {
+        let degrees = degrees.rem_euclid(360);
+        self.rotate = if degrees == 0 { None } else { Some(degrees) };
+        self
+    }
+
+    /// Give the body rows alternating background colours, starting with
+    /// `odd_color` for the first body row. Header rows are left alone.
+    pub fn zebra(&mut self, even_color: &str, odd_color: &str) -> &mut Self {
+        let body = self.rows.iter_mut().filter(|row| !row.is_header);
+
+        for (i, row) in body.enumerate() {
+            // humans count rows from 1
+            if (i + 1) % 2 == 0 {
+                row.background(even_color);
+            } else {
+                row.background(odd_color);
+            }
+        }
+
+        self
+    }
+
+    /// Shade alternating rows with `\rowcolors{start_row}{odd}{even}`,
+    /// starting from the `start_row`'th row (counting from 1).
+    ///
+    /// Unlike `zebra()`, LaTeX picks each row's colour itself, so this only
+    /// adds a single line in front of the environment for any number of rows.
+    pub fn row_colors(&mut self, start_row: usize, odd: &str, even: &str) -> &mut Self {
+        self.row_colors = Some((start_row, odd.to_string(), even.to_string()));
+        self
+    }
+
+    /// Make one of the table's `X` columns wider or narrower than the others
+    /// (e.g. a weight of `2.0` is twice as wide as a normal `X` column).
+    ///
+    /// > **Note:** To keep the table at the correct width, `tabularx` expects
+    /// > the weights of every `X` column to add up to the number of `X`
+    /// > columns. This isn't checked for you.
+    pub fn set_column_weight(
+        &mut self,
+        column_index: usize,
+        weight: f32,
+    ) -> Result<&mut Table, TableError> {
+        let letters = columns::column_letters(&self.column_types);
+        if letters.get(column_index) != Some(&'X') {
+            return Err(TableError::NotAnXColumn(column_index));
+        }
+
+        self.column_weights.retain(|&(i, _)| i != column_index);
+        self.column_weights.push((column_index, weight.to_string()));
+        Ok(self)
+    }
+
+    /// Draw a vertical rule (`|`) after the first `after_column` columns,
+    /// e.g. `2` puts a rule between the second and third columns.
+    ///
+    /// `0` draws the rule before the first column and `column_count()` after
+    /// the last one. The rule is only added to the rendered column
+    /// specification, so `column_types` and the column count are left alone.
+    pub fn add_vertical_rule(&mut self, after_column: usize) -> Result<&mut Table, TableError> {
+        if after_column > self.column_count {
+            return Err(TableError::ColumnIndexOutOfBounds {
+                index: after_column,
+                column_count: self.column_count,
+            });
+        }
+
+        if !self.vertical_rules.contains(&after_column) {
+            self.vertical_rules.push(after_column);
+        }
+        Ok(self)
+    }
+
+    /// The number of rows in this table, including header rows.
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Remove every row, keeping the table's other settings so it can be
+    /// reused.
+    pub fn clear_rows(&mut self) {
+        self.rows.clear();
+    }
+
+    /// Keep the first `len` rows and remove the rest. This does nothing if
+    /// the table has `len` rows or fewer.
+    pub fn truncate_rows(&mut self, len: usize) {
+        self.rows.truncate(len);
+    }
+
+    /// Add another table's rows to the end of this table, e.g. when the data
+    /// arrives in chunks.
+    ///
+    /// Both tables must have the same column specification. The other table's
+    /// header rows become body rows unless `keep_headers` is set, and its
+    /// other settings (e.g. the caption) are ignored.
+    pub fn merge_with(&mut self, other: Table, keep_headers: bool) -> Result<(), TableError> {
+        if other.column_types != self.column_types {
+            return Err(TableError::IncompatibleTables {
+                column_types: self.column_types.clone(),
+                other_column_types: other.column_types,
+            });
+        }
+
+        for mut row in other.rows {
+            if !keep_headers {
+                row.is_header = false;
+                row.is_first_header = false;
+            }
+            self.rows.push(row);
+        }
+
+        Ok(())
+    }
+
+    /// Make the first `count` rows the table's header, clearing the header
+    /// flags on every other row.
+    ///
+    /// The rows are also marked as the header for a long table's first page,
+    /// so a long table shows the same header on every page.
+    pub fn set_header_rows(&mut self, count: usize) -> &mut Self {
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            row.is_header = i < count;
+            row.is_first_header = i < count;
+        }
+        self
+    }
+
+    /// Check every row still has the correct number of columns and its rule
+    /// fits in the table (e.g. after modifying `rows` directly), reporting
+    /// the first row which doesn't.
+    pub fn validate(&self) -> Result<(), TableError> {
+        for (row_index, row) in self.rows.iter().enumerate() {
+            if row.column_count() != self.column_count {
+                return Err(TableError::WrongNumberOfColumnsInRow {
+                    row_index,
+                    provided: row.column_count(),
+                    required: self.column_count,
+                });
+            }
+
+            self.check_rule(row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Pick each column's type based on its contents, using `r` for columns
+    /// where every value is a number and `l` for everything else.
+    ///
+    /// Header rows, blank cells and cells which span several columns are
+    /// ignored, so a column is only right-aligned if it has at least one
+    /// number in it. This replaces the existing column specification,
+    /// including any vertical rules or column weights, and requires every
+    /// row to have the correct number of columns.
+    pub fn auto_columns(&mut self) -> Result<(), TableError> {
+        self.validate()?;
+
+        let mut numeric = vec![None; self.column_count];
+
+        for row in self.body_rows() {
+            let mut column = 0;
+
+            for cell in row.iter() {
+                let value = cell.value.trim();
+                if cell.column_span() == 1 && cell.nested.is_none() && !value.is_empty() {
+                    let is_number = value.parse::<f64>().is_ok();
+                    let previous = numeric[column].unwrap_or(true);
+                    numeric[column] = Some(previous && is_number);
+                }
+
+                column += cell.column_span();
+            }
+        }
+
+        let column_types: String = numeric
+            .into_iter()
+            .map(|is_number| if is_number == Some(true) { 'r' } else { 'l' })
+            .collect();
+        self.set_column_types(&column_types);
+
+        Ok(())
+    }
+
+    /// Iterate over the table's columns, getting the cell in that column from
+    /// each row.
+    ///
+    /// A cell which spans several columns only shows up in the first of
+    /// them, so the other columns have fewer cells. Cells past the last
+    /// column are skipped.
+    pub fn columns(&self) -> impl Iterator<Item = Vec<&Cell>> {
+        let mut columns = vec![Vec::new(); self.column_count];
+
+        for row in &self.rows {
+            let mut column = 0;
+
+            for cell in row.iter() {
+                if let Some(cells) = columns.get_mut(column) {
+                    cells.push(cell);
+                }
+
+                column += cell.column_span();
+            }
+        }
+
+        columns.into_iter()
+    }
+
+    /// Iterate over the table's header rows (the rows marked `is_header`).
+    pub fn header_rows(&self) -> impl Iterator<Item = &Row> {
+        self.rows.iter().filter(|row| row.is_header)
+    }
+
+    /// Iterate over the table's body rows (every row which isn't a header).
+    pub fn body_rows(&self) -> impl Iterator<Item = &Row> {
+        self.rows.iter().filter(|row| !row.is_header)
+    }
+
+    /// Iterate over the rows in this table.
+    pub fn iter(&self) -> Iter<'_, Row> {
+        self.rows.iter()
+    }
+
+    /// Make sure a row fits in the table, without any `\multicolumn` cells
+    /// running past the last column.
+    fn check_row(&self, row: &Row) -> Result<(), TableError> {
+        let mut column_index = 0;
+
+        for cell in row.iter() {
+            let span = cell.column_span();
+            let remaining = self.column_count.saturating_sub(column_index);
+            if cell.multicolumn.is_some() && span > remaining {
+                return Err(TableError::SpanTooWide {
+                    column_index,
+                    span,
+                    remaining,
+                });
+            }
+
+            column_index += span;
+        }
+
+        if row.column_count() != self.column_count {
+            return Err(TableError::WrongNumberOfColumns(
+                row.column_count(),
+                self.column_count,
+            ));
+        }
+
+        self.check_rule(row)
+    }
+
+    /// Make sure the rule under a row only goes across the table's columns.
+    fn check_rule(&self, row: &Row) -> Result<(), TableError> {
+        match row.rule_after.as_ref().and_then(Rule::column_range) {
+            Some((start, end)) if start == 0 || start > end || end > self.column_count => {
+                Err(TableError::RuleOutOfRange {
+                    start,
+                    end,
+                    column_count: self.column_count,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Render the table into a `String` which has been allocated up front
+    /// using `estimate_render_len()`.
+    ///
+    /// This gives the same result as `to_string()`, but avoids reallocating
+    /// as the rendered table grows.
+    pub fn render(&self) -> String {
+        let mut rendered = String::with_capacity(self.estimate_render_len());
+        self.fmt_into(&mut rendered)
+            .expect("Writing to a String can't fail");
+        rendered
+    }
+
+    /// Render the table's rows (with their rules and colours) without the
+    /// surrounding environment, e.g. to put them inside a hand-written
+    /// `tabular`.
+    ///
+    /// This is everything between the `\begin{...}` and `\end{...}` lines,
+    /// so long tables also get their caption and header markers.
+    pub fn render_body(&self) -> String {
+        self.debug_validate();
+        let mut rendered = String::new();
+        self.fmt_body(&mut rendered)
+            .expect("Writing to a String can't fail");
+        rendered
+    }
+
+    /// Render the table with insignificant whitespace removed, so renderings
+    /// can be compared without worrying about spacing (e.g. in snapshot
+    /// tests).
+    ///
+    /// Each run of spaces or tabs becomes a single space and the whitespace
+    /// at either end of each line is trimmed. This is only a testing aid, the
+    /// normal rendering is unaffected.
+    pub fn render_normalized(&self) -> String {
+        let rendered = self.render();
+        let lines: Vec<String> = rendered
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect();
+
+        lines.join("\n")
+    }
+
+    /// Write the table to an `io::Write`.
+    ///
+    /// This writes the table out row by row instead of building up the
+    /// entire rendered table in memory first, which helps with very large
+    /// tables. A table which fails `validate()` is reported as `Error::Table`
+    /// before anything is written, and any IO errors as `Error::Io`.
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> Result<(), Error> {
+        self.validate()?;
+
+        let mut adapter = IoAdapter {
+            writer: w,
+            error: None,
+        };
+
+        match self.fmt_into(&mut adapter) {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                Err(Error::Io(adapter.error.unwrap_or_else(|| {
+                    io::Error::other("Unable to format the table")
+                })))
+            }
+        }
+    }
+
+    /// Create a minimal `article` containing just this table, with all the
+    /// packages it needs, e.g. for quickly turning a table into a PDF.
+    ///
+    /// Use `latex::print()` to render the document.
+    pub fn into_standalone_document(self) -> Document {
+        let mut document = Document::new(DocumentClass::Article);
+        document.add_table(&self);
+        document
+    }
+
+    /// Add any packages this table requires to the document's preamble.
+    pub fn prepare_document(&self, document: &mut Document) {
+        for package in self.kind.packages() {
+            document.preamble.use_package(package);
+        }
+
+        let needs_booktabs = self.rows.iter().any(|row| {
+            row.space_after.is_some()
+                || row.rule_after.as_ref().is_some_and(Rule::requires_booktabs)
+        });
+        if self.booktabs || needs_booktabs {
+            document.preamble.use_package("booktabs");
+        }
+
+        let has_multirow = self
+            .rows
+            .iter()
+            .flat_map(Row::iter)
+            .any(|cell| cell.multirow.is_some());
+        if has_multirow {
+            document.preamble.use_package("multirow");
+        }
+
+        let has_links = self
+            .rows
+            .iter()
+            .flat_map(Row::iter)
+            .any(|cell| cell.link.is_some());
+        if has_links {
+            document.preamble.use_package("hyperref");
+        }
+
+        let symbol_packages =
+            self.rows
+                .iter()
+                .flat_map(Row::iter)
+                .filter_map(|cell| match cell.content {
+                    Content::Symbol(symbol) => symbol.package(),
+                    _ => None,
+                });
+        for package in symbol_packages {
+            document.preamble.use_package(package);
+        }
+
+        if self.uses_captionof() && self.caption.is_some() {
+            document.preamble.use_package("caption");
+        }
+
+        if self.is_sideways() {
+            document.preamble.use_package("rotating");
+        } else if self.rotation().is_some() {
+            document.preamble.use_package("graphicx");
+        }
+
+        if columns::column_letters(&self.column_types).contains(&'S') {
+            document.preamble.use_package("siunitx");
+        }
+
+        let has_colors = self.row_colors.is_some()
+            || self.rows.iter().any(|row| {
+                row.color.is_some()
+                    || row
+                        .iter()
+                        .any(|cell| cell.background.is_some() || cell.text_color.is_some())
+            });
+        if has_colors {
+            document.preamble.use_package_with_option("xcolor", "table");
+        }
+
+        for nested in self
+            .rows
+            .iter()
+            .flat_map(Row::iter)
+            .filter_map(|cell| cell.nested.as_ref())
+        {
+            nested.prepare_document(document);
+        }
+    }
+
+    /// In debug builds, panic if the table fails `validate()` (e.g. after
+    /// modifying `rows` directly) so the mistake is caught early. Release
+    /// builds render the table as best they can.
+    ///
+    /// Both `fmt_into()` and `render_body()` call this, so every way of
+    /// rendering the table as LaTeX is checked.
+    fn debug_validate(&self) {
+        if cfg!(debug_assertions) {
+            if let Err(e) = self.validate() {
+                panic!("Unable to render an invalid table: {}", e);
+            }
+        }
+    }
+
+    /// Write the whole table, wrapping it in a `table` float if necessary.
+    fn fmt_into<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        self.debug_validate();
+        let sideways = self.is_sideways();
+        if self.uses_captionof() {
+            return self.fmt_captionof(f);
+        }
+        if !self.needs_float() {
+            return self.fmt_rotated_environment(f);
+        }
+
+        let float = if sideways { "sidewaystable" } else { "table" };
+        write!(f, r"\begin{{{}}}", float)?;
+        if let Some(ref placement) = self.placement {
+            write!(f, "[{}]", placement)?;
+        }
+        writeln!(f)?;
+
+        if self.centered {
+            writeln!(f, r"\centering")?;
+        }
+
+        if self.caption_position == CaptionPosition::Above {
+            self.fmt_float_caption(f)?;
+        }
+
+        self.fmt_rotated_environment(f)?;
+        writeln!(f)?;
+
+        if self.caption_position == CaptionPosition::Below {
+            self.fmt_float_caption(f)?;
+        }
+
+        write!(f, r"\end{{{}}}", float)
+    }
+
+    /// Write the table with a `\captionof` caption instead of in a float.
+    fn fmt_captionof<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        let has_caption = self.caption.is_some() || self.label.is_some();
+
+        if self.centered {
+            writeln!(f, r"\centering")?;
+        }
+        if has_caption && self.caption_position == CaptionPosition::Above {
+            self.fmt_caption_and_label(f)?;
+            writeln!(f)?;
+        }
+
+        self.fmt_rotated_environment(f)?;
+
+        if has_caption && self.caption_position == CaptionPosition::Below {
+            writeln!(f)?;
+            self.fmt_caption_and_label(f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the caption and label for a `table` float, each on their own
+    /// line.
+    fn fmt_float_caption<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        if let Some(ref caption) = self.caption {
+            writeln!(f, r"\caption{{{}}}", self.caption_text(caption))?;
+        }
+        if let Some(ref label) = self.label {
+            writeln!(f, r"\label{{{}}}", label)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the table's environment, wrapped in a `\rotatebox` if it is
+    /// rotated without a `sidewaystable`.
+    fn fmt_rotated_environment<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        match self.rotation() {
+            Some(degrees) if !self.is_sideways() => {
+                writeln!(f, r"\rotatebox{{{}}}{{", degrees)?;
+                self.fmt_stretched_environment(f)?;
+                write!(f, "\n}}")
+            }
+            _ => self.fmt_stretched_environment(f),
+        }
+    }
+
+    /// Write the table's environment, wrapped in a group which changes
+    /// `\arraystretch` if the rows should be stretched.
+    fn fmt_stretched_environment<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        match self.row_stretch {
+            Some(ref stretch) => {
+                writeln!(f, r"{{\renewcommand{{\arraystretch}}{{{}}}", stretch)?;
+                self.fmt_environment(f)?;
+                write!(f, "\n}}")
+            }
+            None => self.fmt_environment(f),
+        }
+    }
+
+    /// Write the table's environment (`\begin{...}` to `\end{...}`).
+    fn fmt_environment<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        let env = self.kind.environment_name();
+
+        if let Some((start_row, ref odd, ref even)) = self.row_colors {
+            writeln!(f, r"\rowcolors{{{}}}{{{}}}{{{}}}", start_row, odd, even)?;
+        }
+        write!(f, r"\begin{{{}}}", env)?;
+        if let Some(ref options) = self.env_options {
+            write!(f, "[{}]", options)?;
+        }
+        if self.kind.requires_width() {
+            match self.width {
+                Some(ref width) => write!(f, "{{{}}}", width)?,
+                None => write!(f, r"{{\{}}}", self.table_width)?,
+            }
+        }
+        writeln!(f, "{{{}}}", self.column_spec())?;
+        self.fmt_body(f)?;
+        write!(f, r"\end{{{}}}", env)
+    }
+
+    /// Write everything between the `\begin{...}` and `\end{...}` lines.
+    fn fmt_body<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        if self.kind.is_long() && (self.caption.is_some() || self.label.is_some()) {
+            self.fmt_caption_and_label(f)?;
+            writeln!(f, r"\\")?;
+        }
+
+        let widths = if self.pretty {
+            self.pretty_widths()
+        } else {
+            Vec::new()
+        };
+
+        if self.kind.is_long() {
+            self.fmt_long_rows(f, &widths)
+        } else {
+            self.fmt_rows(f, &widths)
+        }
+    }
+
+    /// The width of each column's widest rendered cell, ignoring cells which
+    /// span several columns.
+    fn pretty_widths(&self) -> Vec<usize> {
+        let mut widths = vec![0; self.column_count];
+
+        for row in &self.rows {
+            let mut column = 0;
+
+            for cell in row.iter() {
+                if cell.column_span() == 1 && column < widths.len() {
+                    let width = cell.to_string().chars().count();
+                    widths[column] = widths[column].max(width);
+                }
+                column += cell.column_span();
+            }
+        }
+
+        widths
+    }
+
+    /// The column specification as it should be rendered, with any column
+    /// weights applied.
+    fn column_spec(&self) -> String {
+        // rules go in front of the weights so they stay outside the column
+        let rules = self.vertical_rules.iter().map(|&i| (i, "|".to_string()));
+        let weights = self
+            .column_weights
+            .iter()
+            .map(|&(i, ref weight)| (i, format!(r">{{\setlength\hsize{{{}\hsize}}}}", weight)));
+        let prefixes: Vec<(usize, String)> = rules.chain(weights).collect();
+
+        let mut spec = columns::insert_before_columns(&self.column_types, &prefixes);
+        // there is no column to go in front of after the last one
+        if self.vertical_rules.contains(&self.column_count) {
+            spec.push('|');
+        }
+        spec
+    }
+
+    fn fmt_rows<W: fmt::Write>(&self, f: &mut W, widths: &[usize]) -> fmt::Result {
+        self.fmt_top_rule(f)?;
+
+        // The header rule only makes sense when there is a body to separate the
+        // header from
+        let last_header = self.rows.iter().rposition(|row| row.is_header);
+
+        for (i, row) in self.rows.iter().enumerate() {
+            self.fmt_row(f, row, widths, i + 1 == self.rows.len())?;
+
+            if Some(i) == last_header && i + 1 < self.rows.len() {
+                self.fmt_header_rule(f)?;
+            }
+        }
+
+        if self.booktabs {
+            writeln!(f, r"\bottomrule{}", self.rule_weights[2])?;
+        }
+
+        Ok(())
+    }
+
+    /// Long tables need their headers to come first, marked with
+    /// `\endfirsthead` (the header on the first page) and `\endhead` (the
+    /// header repeated on every other page).
+    fn fmt_long_rows<W: fmt::Write>(&self, f: &mut W, widths: &[usize]) -> fmt::Result {
+        let first_headers: Vec<&Row> = self.rows.iter().filter(|r| r.is_first_header).collect();
+        let mut headers: Vec<&Row> = self
+            .rows
+            .iter()
+            .filter(|r| r.is_header && !r.is_first_header)
+            .collect();
+        // Without a separate header for the later pages, the first page's
+        // header is repeated on them
+        if headers.is_empty() {
+            headers = first_headers
+                .iter()
+                .cloned()
+                .filter(|r| r.is_header)
+                .collect();
+        }
+
+        // A continued caption needs its own header region, even if it has no
+        // rows, otherwise it would show up on the first page too
+        let continued = self.caption_continued.as_ref();
+
+        if !first_headers.is_empty() || continued.is_some() {
+            self.fmt_long_header(f, &first_headers, r"\endfirsthead", widths)?;
+        }
+        if let Some(caption) = continued {
+            writeln!(f, r"\caption*{{{}}}\\", self.caption_text(caption))?;
+        }
+        if !headers.is_empty() || continued.is_some() {
+            self.fmt_long_header(f, &headers, r"\endhead", widths)?;
+        }
+        if first_headers.is_empty() && headers.is_empty() {
+            self.fmt_top_rule(f)?;
+        }
+
+        let body: Vec<&Row> = self
+            .rows
+            .iter()
+            .filter(|r| !r.is_header && !r.is_first_header)
+            .collect();
+        for (i, row) in body.iter().enumerate() {
+            self.fmt_row(f, row, widths, i + 1 == body.len())?;
+        }
+
+        if self.booktabs {
+            writeln!(f, r"\bottomrule{}", self.rule_weights[2])?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_long_header<W: fmt::Write>(
+        &self,
+        f: &mut W,
+        rows: &[&Row],
+        marker: &str,
+        widths: &[usize],
+    ) -> fmt::Result {
+        self.fmt_top_rule(f)?;
+        for row in rows {
+            self.fmt_row(f, row, widths, false)?;
+        }
+        self.fmt_header_rule(f)?;
+
+        writeln!(f, "{}", marker)
+    }
+
+    fn fmt_top_rule<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        if self.booktabs {
+            writeln!(f, r"\toprule{}", self.rule_weights[0])?;
+        }
+        if self.grid {
+            writeln!(f, "{}", Rule::HLine)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the rule separating the header rows from the body.
+    fn fmt_header_rule<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        if self.booktabs {
+            writeln!(f, r"\midrule{}", self.rule_weights[1])?;
+        } else if self.header_rule {
+            writeln!(f, "{}", Rule::HLine)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a row followed by its rule and spacing, padding the cells to
+    /// `widths` when the table is `pretty`. The last row's terminator may be
+    /// left off (see `trailing_row_terminator()`).
+    fn fmt_row<W: fmt::Write>(
+        &self,
+        f: &mut W,
+        row: &Row,
+        widths: &[usize],
+        is_last: bool,
+    ) -> fmt::Result {
+        let followed_by_rule =
+            row.rule_after.is_some() || row.space_after.is_some() || self.grid || self.booktabs;
+        let terminated = self.trailing_row_terminator || !is_last || followed_by_rule;
+
+        row.fmt_padded(f, if self.pretty { widths } else { &[] }, terminated)?;
+        writeln!(f)?;
+
+        match row.rule_after {
+            Some(ref rule) => writeln!(f, "{}", rule)?,
+            None if self.grid => writeln!(f, "{}", Rule::HLine)?,
+            None => {}
+        }
+
+        match row.space_after.as_deref() {
+            Some("") => writeln!(f, r"\addlinespace")?,
+            Some(dimension) => writeln!(f, r"\addlinespace[{}]", dimension)?,
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    fn fmt_caption_and_label<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        let command = if self.uses_captionof() {
+            r"\captionof{table}"
+        } else {
+            r"\caption"
+        };
+
+        if let Some(ref caption) = self.caption {
+            write!(f, "{}{{{}}}", command, self.caption_text(caption))?;
+        }
+        if let Some(ref label) = self.label {
+            write!(f, r"\label{{{}}}", label)?;
+        }
+
+        Ok(())
+    }
+
+    fn caption_text(&self, caption: &str) -> String {
+        if self.raw_captions {
+            caption.to_string()
+        } else {
+            cell::escape(caption)
+        }
+    }
+
+    /// Should the table be captioned with `\captionof` instead of a float?
+    fn uses_captionof(&self) -> bool {
+        self.captionof && !self.kind.is_long()
+    }
+
+    /// The angle the table is rotated by, if it can be rotated.
+    fn rotation(&self) -> Option<i16> {
+        if self.kind.is_long() {
+            None
+        } else {
+            self.rotate
+        }
+    }
+
+    /// Should the table be put in a `sidewaystable` instead of a `table`?
+    /// Tables which aren't floated are rotated with a `\rotatebox` instead.
+    fn is_sideways(&self) -> bool {
+        self.rotation() == Some(90) && self.needs_float() && !self.uses_captionof()
+    }
+
+    /// Normal tables need to be put in a `table` float to be captioned or
+    /// positioned.
+    fn needs_float(&self) -> bool {
+        !self.kind.is_long()
+            && (self.caption.is_some()
+                || self.label.is_some()
+                || self.placement.is_some()
+                || self.centered)
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_into(f)
+    }
+}
+
+impl IntoIterator for Table {
+    type Item = Row;
+    type IntoIter = IntoIter<Row>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Table {
+    type Item = &'a Row;
+    type IntoIter = Iter<'a, Row>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
+
+impl Index<usize> for Table {
+    type Output = Row;
+
+    fn index(&self, index: usize) -> &Row {
+        &self.rows[index]
+    }
+}
+
+impl IndexMut<usize> for Table {
+    /// Get a mutable reference to a row.
+    ///
+    /// > **Note:** The row's column count isn't checked after it has been
+    /// > modified, so `push_row()` should be preferred when adding cells. Use
+    /// > `validate()` to check the table afterwards.
+    fn index_mut(&mut self, index: usize) -> &mut Row {
+        &mut self.rows[index]
+    }
+}
+
+/// Lets the `fmt::Write` based rendering code write to an `io::Write`,
+/// holding on to the underlying IO error if something goes wrong.
+struct IoAdapter<'a, W: 'a> {
+    writer: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write> fmt::Write for IoAdapter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use document::PreambleElement;
+    use print;
+    use std::collections::HashSet;
+
+    fn table_with_rows(kind: TableKind, column_types: &str) -> Table {
+        let mut table = Table::new(kind, "textwidth", column_types);
+
+        let mut first = Row::new();
+        first.push_cell("a").push_cell("b");
+        let mut second = Row::new();
+        second.push_cell("c").push_cell("d");
+
+        table.push_row(first).unwrap().push_row(second).unwrap();
+        table
+    }
+
+    fn uses_package(doc: &Document, name: &str) -> bool {
+        doc.preamble.iter().any(|element| match *element {
+            PreambleElement::UsePackage { ref package, .. } => package == name,
+            _ => false,
+        })
+    }
+
+    #[test]
+    fn table_kind_introspection() {
+        let inputs = vec![
+            (TableKind::Tabular, false, false),
+            (TableKind::Tabularx, true, false),
+            (TableKind::LongTable, false, true),
+            (TableKind::XLTabular, true, true),
+        ];
+
+        for (kind, requires_width, is_long) in inputs {
+            assert_eq!(kind.requires_width(), requires_width, "{:?}", kind);
+            assert_eq!(kind.is_long(), is_long, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn render_tabular() {
+        let should_be = r"\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}";
+        let table = table_with_rows(TableKind::Tabular, "lc");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_tabularx_with_width() {
+        let should_be = r"\begin{tabularx}{\textwidth}{lX}
+a & b\\
+c & d\\
+\end{tabularx}";
+        let table = table_with_rows(TableKind::Tabularx, "lX");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_longtable() {
+        let should_be = r"\begin{longtable}{lc}
+a & b\\
+c & d\\
+\end{longtable}";
+        let table = table_with_rows(TableKind::LongTable, "lc");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_xltabular_with_width() {
+        let should_be = r"\begin{xltabular}{\textwidth}{lX}
+a & b\\
+c & d\\
+\end{xltabular}";
+        let table = table_with_rows(TableKind::XLTabular, "lX");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_xltabular_with_headers() {
+        let should_be = r"\begin{xltabular}{\textwidth}{lX}
+\toprule
+Planet & Description\\
+\midrule
+\endfirsthead
+\toprule
+Planet & Description (continued)\\
+\midrule
+\endhead
+Mercury & The smallest planet\\
+Jupiter & The largest planet\\
+\bottomrule
+\end{xltabular}";
+        let mut table = Table::xltabular("lX");
+        table
+            .push_row(Row::first_header(vec![
+                "Planet".into(),
+                "Description".into(),
+            ]))
+            .unwrap()
+            .push_row(Row::header(vec![
+                "Planet".into(),
+                "Description (continued)".into(),
+            ]))
+            .unwrap()
+            .push_row_values(vec!["Mercury", "The smallest planet"])
+            .unwrap()
+            .push_row_values(vec!["Jupiter", "The largest planet"])
+            .unwrap();
+        table.use_booktabs(true);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_empty_table() {
+        let should_be = "\\begin{tabular}{lr}\n\\end{tabular}";
+        let table = Table::new(TableKind::Tabular, "textwidth", "lr");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn push_row_with_wrong_number_of_columns() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+        let mut row = Row::new();
+        row.push_cell("only one");
+
+        let got = table.push_row(row).unwrap_err();
+
+        assert_eq!(got, TableError::WrongNumberOfColumns(1, 2));
+        assert!(table.rows.is_empty());
+    }
+
+    #[test]
+    fn push_row_values_into_table() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+
+        table
+            .push_row_values(vec!["Name", "Age"])
+            .unwrap()
+            .push_row_values(vec![String::from("Alice"), String::from("42")])
+            .unwrap();
+
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[1].cells[0].value, "Alice");
+    }
+
+    #[test]
+    fn push_row_values_with_wrong_number_of_columns() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+
+        let got = table.push_row_values(vec!["a", "b", "c"]).unwrap_err();
+
+        assert_eq!(got, TableError::WrongNumberOfColumns(3, 2));
+    }
+
+    #[test]
+    fn try_new_with_valid_column_types() {
+        let table = Table::try_new(TableKind::Tabularx, "textwidth", "|l|X|p{3cm}|").unwrap();
+
+        assert_eq!(table.column_count(), 3);
+    }
+
+    #[test]
+    fn set_column_types_recounts_the_columns() {
+        let mut table = Table::tabularx("lX");
+        table.set_column_weight(1, 2.0).unwrap();
+
+        table.set_column_types("|l|c|r@{}S|");
+
+        assert_eq!(table.column_types(), "|l|c|r@{}S|");
+        assert_eq!(table.column_count(), 4);
+        assert!(table.push_row_values(vec!["a", "b", "c", "1"]).is_ok());
+        assert!(table.push_row_values(vec!["a", "b"]).is_err());
+        assert_eq!(
+            table.to_string().lines().next(),
+            Some(r"\begin{tabularx}{\textwidth}{|l|c|r@{}S|}")
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_unknown_column_types() {
+        let got = Table::try_new(TableKind::Tabular, "textwidth", "lXz").unwrap_err();
+
+        assert_eq!(got, TableError::InvalidColumnType('z'));
+    }
+
+    #[test]
+    fn iterate_over_table_rows() {
+        let table = table_with_rows(TableKind::Tabular, "lc");
+
+        let first_cells: Vec<&str> = (&table)
+            .into_iter()
+            .map(|row| row.cells[0].value.as_str())
+            .collect();
+        assert_eq!(first_cells, vec!["a", "c"]);
+
+        let rows: Vec<Row> = table.clone().into_iter().collect();
+        assert_eq!(rows, table.rows);
+    }
+
+    #[test]
+    fn index_into_table() {
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+
+        table[0].is_header = true;
+
+        assert!(table[0].is_header);
+        assert_eq!(table[1].cells[1].value, "d");
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn table_from_rows() {
+        let rows = vec![strings(&["a", "b"]), strings(&["c", "d"])];
+
+        let table = Table::try_from_rows(TableKind::Tabular, "textwidth", "lc", rows).unwrap();
+
+        assert_eq!(table, table_with_rows(TableKind::Tabular, "lc"));
+    }
+
+    #[test]
+    fn table_from_ragged_rows() {
+        let rows = vec![
+            strings(&["a", "b"]),
+            strings(&["c", "d"]),
+            strings(&["e", "f", "g"]),
+        ];
+
+        let got = Table::try_from_rows(TableKind::Tabular, "textwidth", "lc", rows).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::WrongNumberOfColumnsInRow {
+                row_index: 2,
+                provided: 3,
+                required: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn weighted_x_columns() {
+        let mut table = Table::new(TableKind::Tabularx, "textwidth", "lXX");
+
+        table.set_column_weight(1, 1.5).unwrap();
+        table.set_column_weight(2, 0.5).unwrap();
+
+        assert_eq!(
+            table.column_spec(),
+            r"l>{\setlength\hsize{1.5\hsize}}X>{\setlength\hsize{0.5\hsize}}X"
+        );
+        assert!(table.to_string().starts_with(
+            r"\begin{tabularx}{\textwidth}{l>{\setlength\hsize{1.5\hsize}}X>{\setlength\hsize{0.5\hsize}}X}"
+        ));
+    }
+
+    #[test]
+    fn reweighting_a_column_replaces_the_old_weight() {
+        let mut table = Table::new(TableKind::Tabularx, "textwidth", "XX");
+
+        table.set_column_weight(0, 1.5).unwrap();
+        table.set_column_weight(0, 2.0).unwrap();
+
+        assert_eq!(table.column_spec(), r">{\setlength\hsize{2\hsize}}XX");
+    }
+
+    #[test]
+    fn only_x_columns_can_be_weighted() {
+        let mut table = Table::new(TableKind::Tabularx, "textwidth", "lX");
+
+        assert_eq!(
+            table.set_column_weight(0, 2.0),
+            Err(TableError::NotAnXColumn(0))
+        );
+        assert_eq!(
+            table.set_column_weight(5, 2.0),
+            Err(TableError::NotAnXColumn(5))
+        );
+    }
+
+    #[test]
+    fn push_row_into_table_with_paragraph_columns() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lp{3cm}");
+        let mut row = Row::new();
+        row.push_cell("short")
+            .push_cell("a much longer description");
+
+        assert_eq!(table.column_count(), 2);
+        assert!(table.push_row(row).is_ok());
+    }
+
+    #[test]
+    fn multicolumn_cells_count_towards_row_width() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lllll");
+        let mut row = Row::new();
+        row.cells
+            .push(Cell::multicolumn(3, 'c', "Title".to_string()));
+        row.push_cell("a").push_cell("b");
+
+        assert!(table.push_row(row).is_ok());
+    }
+
+    #[test]
+    fn bordered_multicolumn_cell() {
+        let mut table = Table::tabular("|l|l|l|l|l|");
+        let mut row = Row::new();
+        row.push("a")
+            .push(Cell::multicolumn_with_spec(2, "|c|", "Title".to_string()))
+            .push("b")
+            .push("c");
+
+        table.push_row(row).unwrap();
+
+        assert_eq!(
+            table.rows[0].to_string(),
+            r"a & \multicolumn{2}{|c|}{Title} & b & c\\"
+        );
+    }
+
+    #[test]
+    fn multicolumn_cells_cant_run_past_the_last_column() {
+        let mut table = Table::tabular("lllll");
+        let mut row: Row = vec!["a", "b", "c", "d"].into_iter().collect();
+        row.push(Cell::multicolumn_with_spec(2, "|c|", "Title".to_string()));
+
+        let got = table.push_row(row).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::SpanTooWide {
+                column_index: 4,
+                span: 2,
+                remaining: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn multicolumn_cells_with_wrong_total_width() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lllll");
+        let mut row = Row::new();
+        row.cells
+            .push(Cell::multicolumn(3, 'c', "Title".to_string()));
+        row.push_cell("a");
+
+        let got = table.push_row(row).unwrap_err();
+
+        assert_eq!(got, TableError::WrongNumberOfColumns(4, 5));
+    }
+
+    #[test]
+    fn multirow_cells_add_package_to_preamble() {
+        let mut doc = Document::new(DocumentClass::Article);
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+
+        let mut first = Row::new();
+        first
+            .cells
+            .push(Cell::multirow(2, "*", "Group".to_string()));
+        first.push_cell("a");
+        let mut second = Row::new();
+        second.cells.push(Cell::default());
+        second.push_cell("b");
+        table.push_row(first).unwrap().push_row(second).unwrap();
+
+        table.prepare_document(&mut doc);
+
+        assert!(uses_package(&doc, "multirow"));
+    }
+
+    #[test]
+    fn plain_tables_dont_need_multirow() {
+        let mut doc = Document::new(DocumentClass::Article);
+        let table = table_with_rows(TableKind::Tabular, "lc");
+
+        table.prepare_document(&mut doc);
+
+        assert!(!uses_package(&doc, "multirow"));
+    }
+
+    #[test]
+    fn captioned_tabular_is_wrapped_in_a_float() {
+        let should_be = r"\begin{table}
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}
+\caption{Results for 50\% of runs}
+\label{tab:results}
+\end{table}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table
+            .caption("Results for 50% of runs")
+            .label("tab:results");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn captioned_longtable_stays_inside_the_environment() {
+        let should_be = r"\begin{longtable}{lc}
+\caption{Results}\label{tab:results}\\
+a & b\\
+c & d\\
+\end{longtable}";
+        let mut table = table_with_rows(TableKind::LongTable, "lc");
+        table.caption("Results").label("tab:results");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn float_with_placement() {
+        let should_be = r"\begin{table}[h!]
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}
+\caption{Results}
+\end{table}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.caption("Results").placement("h!");
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn centered_float() {
+        let should_be = r"\begin{table}[htbp]
+\centering
+\begin{tabularx}{\textwidth}{lX}
+a & b\\
+c & d\\
+\end{tabularx}
+\end{table}";
+        let mut table = table_with_rows(TableKind::Tabularx, "lX");
+        table.placement("htbp").centered(true);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn longtables_ignore_placement_and_centering() {
+        let mut table = table_with_rows(TableKind::LongTable, "lc");
+        let should_be = table.to_string();
+        table.placement("h!").centered(true);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn longtable_header_markers() {
+        let should_be = r"\begin{longtable}{lc}
+First & Header\\
+\endfirsthead
+Repeated & Header\\
+\endhead
+a & b\\
+c & d\\
+\end{longtable}";
+        let mut table = Table::new(TableKind::LongTable, "textwidth", "lc");
+
+        let mut first_header = Row::new();
+        first_header.push_cell("First").push_cell("Header");
+        first_header.is_header = true;
+        first_header.is_first_header = true;
+        let mut header = Row::new();
+        header.push_cell("Repeated").push_cell("Header");
+        header.is_header = true;
+
+        table
+            .push_row(first_header)
+            .unwrap()
+            .push_row(header)
+            .unwrap();
+        let body = table_with_rows(TableKind::LongTable, "lc").rows;
+        table.rows.extend(body);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn normal_tables_ignore_header_markers() {
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rows[0].is_header = true;
+        table.rows[0].is_first_header = true;
+
+        let got = table.to_string();
+
+        assert!(!got.contains(r"\endfirsthead"));
+        assert!(!got.contains(r"\endhead"));
+    }
+
+    #[test]
+    fn write_to_matches_display() {
+        let mut table = table_with_rows(TableKind::Tabularx, "lX");
+        table.rows[0].is_header = true;
+        table.use_booktabs(true).caption("Streamed").centered(true);
+        let mut buffer = Vec::new();
+
+        table.write_to(&mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), table.to_string());
+    }
+
+    #[test]
+    fn write_to_reports_io_errors() {
+        struct FailingSink;
+
+        impl io::Write for FailingSink {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let table = table_with_rows(TableKind::Tabular, "lc");
+
+        match table.write_to(&mut FailingSink) {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::BrokenPipe),
+            other => panic!("Expected an IO error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rules_after_rows() {
+        let should_be = r"\begin{tabular}{lc}
+a & b\\
+\hline
+c & d\\
+\cline{1-2}
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rows[0].rule_after = Some(Rule::HLine);
+        table.rows[1].rule_after = Some(Rule::CLine(1, 2));
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn booktabs_rules_after_rows() {
+        let should_be = r"\begin{longtable}{lc}
+a & b\\
+\cmidrule{2-2}
+c & d\\
+\midrule
+\end{longtable}";
+        let mut table = table_with_rows(TableKind::LongTable, "lc");
+        table.rows[0].rule_after = Some(Rule::CMidRule(2, 2, Trim::None));
+        table.rows[1].rule_after = Some(Rule::MidRule);
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        assert!(uses_package(&doc, "booktabs"));
+    }
+
+    #[test]
+    fn space_between_groups_of_rows() {
+        let should_be = r"\begin{tabular}{lc}
+a & b\\
+\midrule
+\addlinespace[1ex]
+c & d\\
+\addlinespace
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rows[0].rule_after = Some(Rule::MidRule);
+        table.rows[0].space_after("1ex");
+        table.rows[1].space_after("");
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        assert!(uses_package(&doc, "booktabs"));
+    }
+
+    #[test]
+    fn hline_doesnt_need_booktabs() {
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rows[0].rule_after = Some(Rule::HLine);
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert!(!uses_package(&doc, "booktabs"));
+    }
+
+    #[test]
+    fn zebra_striped_table() {
+        let should_be = r"\begin{tabular}{lc}
+Name & Value\\
+\rowcolor{white} a & b\\
+\rowcolor{gray} c & d\\
+\rowcolor{white} e & f\\
+\end{tabular}";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lc");
+        table
+            .push_row_values(vec!["Name", "Value"])
+            .unwrap()
+            .push_row_values(vec!["a", "b"])
+            .unwrap()
+            .push_row_values(vec!["c", "d"])
+            .unwrap()
+            .push_row_values(vec!["e", "f"])
+            .unwrap();
+        table.rows[0].is_header = true;
+        table.zebra("gray", "white");
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        let xcolor = PreambleElement::UsePackage {
+            package: "xcolor".to_string(),
+            argument: Some("table".to_string()),
+        };
+        assert!(doc.preamble.iter().any(|element| *element == xcolor));
+    }
+
+    #[test]
+    fn cell_backgrounds_need_xcolor() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "l");
+        let row = Row::from_cells(vec![Cell::with_background("x".to_string(), "red")]);
+        table.push_row(row).unwrap();
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.rows[0].to_string(), r"\cellcolor{red}x\\");
+        let xcolor = PreambleElement::UsePackage {
+            package: "xcolor".to_string(),
+            argument: Some("table".to_string()),
+        };
+        assert!(doc.preamble.iter().any(|element| *element == xcolor));
+    }
+
+    #[test]
+    fn s_columns_need_siunitx() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lS");
+        let mut row = Row::new();
+        row.push_cell("g");
+        row.cells.push(Cell::numeric(9.81));
+        table.push_row(row).unwrap();
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert!(uses_package(&doc, "siunitx"));
+    }
+
+    #[test]
+    fn siunitx_is_only_loaded_when_needed() {
+        let table = Table::new(TableKind::Tabular, "textwidth", "lcr");
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert!(!uses_package(&doc, "siunitx"));
+    }
+
+    fn packages_for(kind: TableKind) -> Vec<String> {
+        let table = Table::new(kind, "textwidth", "lc");
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        doc.preamble
+            .iter()
+            .map(|element| match *element {
+                PreambleElement::UsePackage { ref package, .. } => package.clone(),
+                ref other => panic!("Unexpected preamble element: {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tabular_doesnt_need_any_packages() {
+        assert!(packages_for(TableKind::Tabular).is_empty());
+    }
+
+    #[test]
+    fn tabularx_loads_tabularx() {
+        assert_eq!(packages_for(TableKind::Tabularx), vec!["tabularx"]);
+    }
+
+    #[test]
+    fn longtable_loads_longtable() {
+        assert_eq!(packages_for(TableKind::LongTable), vec!["longtable"]);
+    }
+
+    #[test]
+    fn xltabular_loads_tabularx_and_longtable() {
+        assert_eq!(
+            packages_for(TableKind::XLTabular),
+            vec!["tabularx", "longtable", "xltabular"]
+        );
+    }
+
+    #[test]
+    fn xltabular_loads_its_dependencies_first() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.add_table(&Table::longtable("l"))
+            .add_table(&Table::xltabular("X"))
+            .add_table(&Table::tabularx("X"));
+
+        let rendered = print(&doc).unwrap();
+        let position = |package: &str| {
+            rendered
+                .find(&format!(r"\usepackage{{{}}}", package))
+                .unwrap()
+        };
+
+        assert!(position("longtable") < position("xltabular"));
+        assert!(position("tabularx") < position("xltabular"));
+        assert_eq!(
+            TableKind::XLTabular.packages(),
+            &["tabularx", "longtable", "xltabular"]
+        );
+    }
+
+    #[test]
+    fn booktabs_separates_header_from_body() {
+        let should_be = r"\begin{tabular}{lc}
+\toprule
+a & b\\
+\midrule
+c & d\\
+\bottomrule
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rows[0].is_header = true;
+        table.use_booktabs(true);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn booktabs_rules_with_weights() {
+        let should_be = r"\begin{tabular}{lc}
+\toprule[\heavyrulewidth]
+a & b\\
+\midrule
+c & d\\
+\bottomrule[0.12em]
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rows[0].is_header = true;
+        table.use_booktabs(true).booktabs_rule_weights(
+            RuleWeight::Heavy,
+            RuleWeight::Default,
+            RuleWeight::Custom("0.12em".to_string()),
+        );
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_rule_weights() {
+        assert_eq!(RuleWeight::Default.to_string(), "");
+        assert_eq!(RuleWeight::Light.to_string(), r"[\lightrulewidth]");
+        assert_eq!(RuleWeight::Custom("1pt".to_string()).to_string(), "[1pt]");
+    }
+
+    #[test]
+    fn booktabs_without_header_rows() {
+        let should_be = r"\begin{tabular}{lc}
+\toprule
+a & b\\
+c & d\\
+\bottomrule
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.use_booktabs(true);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn booktabs_with_only_header_rows() {
+        let should_be = r"\begin{tabular}{lc}
+\toprule
+a & b\\
+c & d\\
+\bottomrule
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        for row in &mut table.rows {
+            row.is_header = true;
+        }
+        table.use_booktabs(true);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn booktabs_adds_package_to_preamble() {
+        let mut doc = Document::new(DocumentClass::Article);
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+        table.use_booktabs(true);
+
+        table.prepare_document(&mut doc);
+
+        assert!(uses_package(&doc, "booktabs"));
+    }
+
+    #[test]
+    fn add_table_to_document() {
+        let mut doc = Document::new(DocumentClass::Article);
+        let table = table_with_rows(TableKind::Tabularx, "XX");
+
+        doc.add_table(&table);
+        let rendered = print(&doc).unwrap();
+
+        assert!(rendered.contains(r"\usepackage{tabularx}"));
+        assert!(rendered.contains(r"\begin{tabularx}{\textwidth}{XX}"));
+        assert!(rendered.contains(r"\end{tabularx}"));
+    }
+
+    #[test]
+    fn paragraph_cells_are_a_single_column() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lp{2cm}");
+        let row = Row::from_cells(vec![
+            Cell::default(),
+            Cell::paragraph("x".to_string(), "4cm", VAlign::Bottom),
+        ]);
+
+        assert!(table.push_row(row).is_ok());
+    }
+
+    #[test]
+    fn push_row_without_checking_its_width() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+        let row: Row = vec!["a"].into_iter().collect();
+
+        table.push_row_unchecked(row);
+
+        assert_eq!(table.rows.len(), 1);
+        assert_eq!(table.rows[0].column_count(), 1);
+    }
+
+    #[test]
+    fn push_rows_or_panic() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+
+        table
+            .push_row_or_panic(vec!["a", "b"].into_iter().collect())
+            .push_row_or_panic(vec!["c", "d"].into_iter().collect());
+
+        assert_eq!(table.rows.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unable to add row 1 to the table")]
+    fn push_row_or_panic_with_wrong_width() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+
+        table
+            .push_row_or_panic(vec!["a", "b"].into_iter().collect())
+            .push_row_or_panic(vec!["c"].into_iter().collect());
+    }
+
+    #[test]
+    fn render_table_with_column_decorators() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", r">{\bfseries}lc");
+        table.push_row_values(vec!["a", "b"]).unwrap();
+
+        let should_be = r"\begin{tabular}{>{\bfseries}lc}
+a & b\\
+\end{tabular}";
+
+        assert_eq!(table.column_count(), 2);
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn validate_a_table() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.rows[1].cells[0] = Cell::multicolumn(2, 'c', "wide".to_string());
+        table.rows[1].cells.pop();
+
+        assert_eq!(table.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_catches_corrupted_rows() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.rows[1].push_cell("extra");
+        table.rows.push(Row::new());
+
+        let got = table.validate().unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::WrongNumberOfColumnsInRow {
+                row_index: 1,
+                provided: 3,
+                required: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn render_caption_below_float() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.caption("Results");
+
+        let should_be = r"\begin{table}
+\begin{tabular}{ll}
+a & b\\
+c & d\\
+\end{tabular}
+\caption{Results}
+\end{table}";
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_caption_above_float() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table
+            .caption("Results")
+            .label("tab:results")
+            .caption_position(CaptionPosition::Above);
+
+        let should_be = r"\begin{table}
+\caption{Results}
+\label{tab:results}
+\begin{tabular}{ll}
+a & b\\
+c & d\\
+\end{tabular}
+\end{table}";
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn table_from_structured_columns() {
+        let columns = vec![
+            Column::Left,
+            Column::separator(""),
+            Column::Right,
+            Column::VerticalRule,
+            Column::Center,
+        ];
+
+        let table = Table::from_columns(TableKind::Tabular, "textwidth", columns);
+
+        assert_eq!(table.column_types, "l@{}r|c");
+        assert_eq!(table.column_count(), 3);
+        assert!(table.to_string().starts_with(r"\begin{tabular}{l@{}r|c}"));
+    }
+
+    #[test]
+    fn rule_after_the_last_row() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.rows[1].rule_after = Some(Rule::HLine);
+
+        let should_be = r"\begin{tabular}{ll}
+a & b\\
+c & d\\
+\hline
+\end{tabular}";
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_grid() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.push_row_values(vec!["e", "f"]).unwrap();
+        table.grid(true);
+
+        let should_be = r"\begin{tabular}{ll}
+\hline
+a & b\\
+\hline
+c & d\\
+\hline
+e & f\\
+\hline
+\end{tabular}";
+
+        let got = table.to_string();
+
+        assert_eq!(got, should_be);
+        assert_eq!(got.matches(r"\hline").count(), 4);
+    }
+
+    #[test]
+    fn render_long_table_with_continued_caption() {
+        let mut table = table_with_rows(TableKind::LongTable, "ll");
+        table.rows[0].is_header = true;
+        table.rows[0].is_first_header = true;
+        table.rows.insert(1, vec!["a", "b"].into_iter().collect());
+        table.rows[1].is_header = true;
+        table
+            .caption("Results")
+            .caption_continued("Results (continued)");
+
+        let should_be = r"\begin{longtable}{ll}
+\caption{Results}\\
+a & b\\
+\endfirsthead
+\caption*{Results (continued)}\\
+a & b\\
+\endhead
+c & d\\
+\end{longtable}";
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_width_presets() {
+        let inputs = vec![
+            (TableWidth::TextWidth, r"\begin{tabularx}{\textwidth}{XX}"),
+            (TableWidth::LineWidth, r"\begin{tabularx}{\linewidth}{XX}"),
+            (
+                TableWidth::ColumnWidth,
+                r"\begin{tabularx}{\columnwidth}{XX}",
+            ),
+            (
+                TableWidth::Absolute("12cm".to_string()),
+                r"\begin{tabularx}{12cm}{XX}",
+            ),
+        ];
+
+        for (width, should_be) in inputs {
+            let mut table = table_with_rows(TableKind::Tabularx, "XX");
+            table.set_width(width);
+
+            assert!(table.to_string().starts_with(should_be));
+        }
+    }
+
+    #[test]
+    fn tabular_ignores_its_width() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.set_width(TableWidth::Absolute("12cm".to_string()));
+
+        assert!(table.to_string().starts_with(r"\begin{tabular}{ll}"));
+    }
+
+    #[test]
+    fn nested_tables_load_their_packages() {
+        let inner = Table::new(TableKind::Tabular, "textwidth", "S");
+        let mut outer = Table::new(TableKind::Tabular, "textwidth", "ll");
+        let row = Row::from_cells(vec![Cell::from("a"), Cell::nested(inner)]);
+        outer.push_row(row).unwrap();
+        let mut doc = Document::new(DocumentClass::Article);
+
+        outer.prepare_document(&mut doc);
+
+        assert!(uses_package(&doc, "siunitx"));
+    }
+
+    #[test]
+    fn quick_constructors() {
+        let inputs = vec![
+            (Table::tabular("lcr"), TableKind::Tabular, 3),
+            (Table::tabularx("lX"), TableKind::Tabularx, 2),
+            (Table::longtable("p{3cm}l"), TableKind::LongTable, 2),
+            (Table::xltabular("XXXX"), TableKind::XLTabular, 4),
+        ];
+
+        for (table, kind, column_count) in inputs {
+            assert_eq!(table.kind, kind);
+            assert_eq!(table.column_count(), column_count);
+            assert_eq!(table.table_width, "textwidth");
+        }
+    }
+
+    #[test]
+    fn clear_rows() {
+        let mut table = table_with_rows(TableKind::Tabularx, "lX");
+        assert_eq!(table.row_count(), 2);
+
+        table.clear_rows();
+
+        assert_eq!(table.row_count(), 0);
+        assert_eq!(table.column_count(), 2);
+        assert_eq!(table.column_types, "lX");
+        assert_eq!(table.kind, TableKind::Tabularx);
+    }
+
+    #[test]
+    fn truncate_rows() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+
+        table.truncate_rows(5);
+        assert_eq!(table.row_count(), 2);
+
+        table.truncate_rows(1);
+        assert_eq!(table.row_count(), 1);
+        assert_eq!(table.rows[0].cells[0].value, "a");
+        assert_eq!(table.column_count(), 2);
+    }
+
+    #[test]
+    fn insert_row_in_the_middle() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        let row: Row = vec!["x", "y"].into_iter().collect();
+
+        table.insert_row(1, row).unwrap();
+
+        let values: Vec<&str> = table
+            .iter()
+            .map(|row| row.cells[0].value.as_str())
+            .collect();
+        assert_eq!(values, vec!["a", "x", "c"]);
+    }
+
+    #[test]
+    fn insert_row_checks_its_arguments() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+
+        let got = table
+            .insert_row(3, vec!["x", "y"].into_iter().collect())
+            .unwrap_err();
+        assert_eq!(
+            got,
+            TableError::RowIndexOutOfBounds {
+                index: 3,
+                row_count: 2,
+            }
+        );
+
+        let got = table
+            .insert_row(0, vec!["x"].into_iter().collect())
+            .unwrap_err();
+        assert_eq!(got, TableError::WrongNumberOfColumns(1, 2));
+        assert_eq!(table.row_count(), 2);
+    }
+
+    #[test]
+    fn remove_row() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+
+        let removed = table.remove_row(0).unwrap();
+
+        assert_eq!(removed.cells[0].value, "a");
+        assert_eq!(table.row_count(), 1);
+        assert_eq!(table.remove_row(1), None);
+    }
+
+    #[test]
+    fn automatically_pick_column_types() {
+        let mut table = Table::tabular("lll");
+        let mut header: Row = vec!["Name", "Age", "Notes"].into_iter().collect();
+        header.is_header = true;
+        table.push_row(header).unwrap();
+        table.push_row_values(vec!["Alice", "42", "1"]).unwrap();
+        table.push_row_values(vec!["Bob", "-7.5", "n/a"]).unwrap();
+        table.push_row_values(vec!["Carol", "", "3"]).unwrap();
+
+        table.auto_columns().unwrap();
+
+        assert_eq!(table.column_types, "lrl");
+        assert_eq!(table.column_count(), 3);
+    }
+
+    #[test]
+    fn empty_columns_are_left_aligned() {
+        let mut table = Table::tabular("rr");
+
+        table.auto_columns().unwrap();
+
+        assert_eq!(table.column_types, "ll");
+    }
+
+    #[test]
+    fn auto_columns_needs_a_valid_table() {
+        let mut table = Table::tabular("ll");
+        table.rows.push(vec!["1"].into_iter().collect());
+
+        assert!(table.auto_columns().is_err());
+        assert_eq!(table.column_types, "ll");
+    }
+
+    #[test]
+    fn normalized_rendering_ignores_whitespace() {
+        let mut left = Table::tabular("ll");
+        left.push_row_values(vec!["a", "b c"]).unwrap();
+        let mut right = Table::tabular("ll");
+        right.push_row_values(vec![" a  ", "b \t c"]).unwrap();
+
+        let should_be = r"\begin{tabular}{ll}
+a & b c\\
+\end{tabular}";
+
+        assert_ne!(left.to_string(), right.to_string());
+        assert_eq!(left.render_normalized(), right.render_normalized());
+        assert_eq!(left.render_normalized(), should_be);
+    }
+
+    #[test]
+    fn render_stretched_rows() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.row_stretch(1.3);
+
+        let should_be = r"{\renewcommand{\arraystretch}{1.3}
+\begin{tabular}{ll}
+a & b\\
+c & d\\
+\end{tabular}
+}";
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_each_border_style() {
+        let inputs = vec![
+            (BorderStyle::None, "a & b\\\\\nc & d\\\\\n"),
+            (
+                BorderStyle::Booktabs,
+                "\\toprule\na & b\\\\\n\\midrule\nc & d\\\\\n\\bottomrule\n",
+            ),
+            (
+                BorderStyle::Grid,
+                "\\hline\na & b\\\\\n\\hline\nc & d\\\\\n\\hline\n",
+            ),
+            (BorderStyle::HeaderOnly, "a & b\\\\\n\\hline\nc & d\\\\\n"),
+        ];
+
+        for (style, rows) in inputs {
+            let mut table = table_with_rows(TableKind::Tabular, "ll");
+            table.rows[0].is_header = true;
+            table.use_booktabs(true).with_border_style(style);
+
+            let should_be = format!("\\begin{{tabular}}{{ll}}\n{}\\end{{tabular}}", rows);
+
+            assert_eq!(table.to_string(), should_be, "{:?}", style);
+        }
+    }
+
+    #[test]
+    fn only_booktabs_borders_need_booktabs() {
+        for &style in &[
+            BorderStyle::None,
+            BorderStyle::Grid,
+            BorderStyle::HeaderOnly,
+        ] {
+            let mut table = table_with_rows(TableKind::Tabular, "ll");
+            table.with_border_style(style);
+            let mut doc = Document::new(DocumentClass::Article);
+
+            table.prepare_document(&mut doc);
+
+            assert!(!uses_package(&doc, "booktabs"));
+        }
+    }
+
+    #[test]
+    fn set_header_rows() {
+        let mut table = table_with_rows(TableKind::LongTable, "ll");
+        table.push_row_values(vec!["e", "f"]).unwrap();
+        table.rows[2].is_header = true;
+
+        table.set_header_rows(2);
+
+        let flags: Vec<(bool, bool)> = table
+            .iter()
+            .map(|row| (row.is_header, row.is_first_header))
+            .collect();
+        assert_eq!(flags, vec![(true, true), (true, true), (false, false)]);
+    }
+
+    #[test]
+    fn repeat_header_rows_on_every_long_table_page() {
+        let one_row = r"\begin{longtable}{ll}
+a & b\\
+\endfirsthead
+a & b\\
+\endhead
+c & d\\
+e & f\\
+\end{longtable}";
+        let two_rows = r"\begin{longtable}{ll}
+a & b\\
+c & d\\
+\endfirsthead
+a & b\\
+c & d\\
+\endhead
+e & f\\
+\end{longtable}";
+        let mut table = table_with_rows(TableKind::LongTable, "ll");
+        table.push_row_values(vec!["e", "f"]).unwrap();
+
+        table.set_header_rows(1);
+        assert_eq!(table.to_string(), one_row);
+
+        table.set_header_rows(2);
+        assert_eq!(table.to_string(), two_rows);
+    }
+
+    #[test]
+    fn clear_header_rows() {
+        let mut table = table_with_rows(TableKind::LongTable, "ll");
+        table.set_header_rows(1);
+
+        table.set_header_rows(0);
+
+        assert!(table
+            .iter()
+            .all(|row| !row.is_header && !row.is_first_header));
+    }
+
+    #[test]
+    fn captions_are_escaped_like_cells() {
+        let mut table = Table::tabularx("XX");
+        table
+            .push_row_values(vec!["a", "b"])
+            .unwrap()
+            .caption("50% off & more")
+            .label("tab:sale_items");
+
+        let should_be = r"\begin{table}
+\begin{tabularx}{\textwidth}{XX}
+a & b\\
+\end{tabularx}
+\caption{50\% off \& more}
+\label{tab:sale_items}
+\end{table}";
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn raw_captions_arent_escaped() {
+        let mut table = table_with_rows(TableKind::LongTable, "ll");
+        table
+            .caption(r"Results for $\alpha$")
+            .caption_continued(r"\emph{continued}")
+            .raw_captions(true);
+
+        let got = table.to_string();
+
+        assert!(got.contains(r"\caption{Results for $\alpha$}"));
+        assert!(got.contains(r"\caption*{\emph{continued}}"));
+    }
+
+    #[test]
+    fn render_into_a_preallocated_string() {
+        let mut table = table_with_rows(TableKind::Tabularx, "XX");
+        table.caption("Results");
+
+        let got = table.render();
+
+        assert_eq!(got, table.to_string());
+        assert!(got.capacity() >= got.len());
+    }
+
+    #[test]
+    fn wrap_a_table_in_raw_latex() {
+        let mut doc = Document::new(DocumentClass::Article);
+        let table = table_with_rows(TableKind::Tabular, "ll");
+
+        doc.add_raw(r"\begin{adjustbox}{max width=\textwidth}")
+            .add_table(&table)
+            .add_raw(r"\end{adjustbox}");
+        let rendered = print(&doc).unwrap();
+
+        let should_be = r"\begin{adjustbox}{max width=\textwidth}
+\begin{tabular}{ll}
+a & b\\
+c & d\\
+\end{tabular}
+\end{adjustbox}
+";
+        assert!(rendered.contains(should_be), "{}", rendered);
+    }
+
+    #[test]
+    fn get_column_alignments() {
+        let table = Table::tabularx("lcrXp{2cm}");
+
+        let got: Vec<Option<Column>> = (0..6).map(|i| table.column_alignment(i)).collect();
+
+        let should_be = vec![
+            Some(Column::Left),
+            Some(Column::Center),
+            Some(Column::Right),
+            Some(Column::X),
+            Some(Column::Para {
+                width: "2cm".to_string(),
+                valign: VAlign::Top,
+            }),
+            None,
+        ];
+        assert_eq!(got, should_be);
+    }
+
+    #[test]
+    fn render_only_the_body() {
+        let should_be = r"\toprule
+Name & Age\\
+\midrule
+\rowcolor{gray!10} Alice & 42\\
+\hline
+\bottomrule
+";
+        let mut table = Table::tabular("lr");
+        table
+            .push_row_values(vec!["Name", "Age"])
+            .unwrap()
+            .push_row_values(vec!["Alice", "42"])
+            .unwrap()
+            .use_booktabs(true)
+            .caption("People");
+        table.rows[0].is_header = true;
+        table.rows[1].background("gray!10").rule_after = Some(Rule::HLine);
+
+        let body = table.render_body();
+        let rendered = table.render();
+        let start = rendered.find("{lr}\n").unwrap() + "{lr}\n".len();
+        let end = rendered.find(r"\end{tabular}").unwrap();
+
+        assert_eq!(body, should_be);
+        assert_eq!(body, &rendered[start..end]);
+    }
+
+    #[test]
+    fn iterate_over_columns() {
+        let mut table = Table::tabular("lcr");
+        table
+            .push_row_values(vec!["a", "b", "c"])
+            .unwrap()
+            .push_row_values(vec!["d", "e", "f"])
+            .unwrap();
+
+        let got: Vec<Vec<&str>> = table
+            .columns()
+            .map(|cells| cells.iter().map(|cell| cell.value.as_str()).collect())
+            .collect();
+
+        assert_eq!(got, vec![vec!["a", "d"], vec!["b", "e"], vec!["c", "f"]]);
+    }
+
+    #[test]
+    fn spanning_cells_are_in_their_first_column() {
+        let mut table = Table::tabular("lcr");
+        table
+            .push_row(Row::from_cells(vec![
+                Cell::multicolumn(2, 'c', "wide".to_string()),
+                Cell::from("c"),
+            ]))
+            .unwrap()
+            .push_row_values(vec!["d", "e", "f"])
+            .unwrap();
+
+        let lengths: Vec<usize> = table.columns().map(|cells| cells.len()).collect();
+
+        assert_eq!(lengths, vec![2, 1, 2]);
+    }
+
+    #[test]
+    fn merge_two_tables() {
+        let mut first = table_with_rows(TableKind::Tabular, "lc");
+        first.set_header_rows(1);
+        let mut second = table_with_rows(TableKind::Tabular, "lc");
+        second.set_header_rows(1);
+        let mut with_headers = first.clone();
+
+        first.merge_with(second.clone(), false).unwrap();
+        with_headers.merge_with(second, true).unwrap();
+
+        let headers: Vec<bool> = first.iter().map(|row| row.is_header).collect();
+        assert_eq!(headers, vec![true, false, false, false]);
+        assert_eq!(first.rows[2].cells[0].value, "a");
+        let headers: Vec<bool> = with_headers.iter().map(|row| row.is_header).collect();
+        assert_eq!(headers, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn cant_merge_tables_with_different_columns() {
+        let mut first = table_with_rows(TableKind::Tabular, "lc");
+        let second = Table::tabular("lcr");
+
+        let got = first.merge_with(second, false).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::IncompatibleTables {
+                column_types: "lc".to_string(),
+                other_column_types: "lcr".to_string(),
+            }
+        );
+        assert_eq!(first.row_count(), 2);
+    }
+
+    #[test]
+    fn packages_are_only_loaded_once() {
+        let mut table = table_with_rows(TableKind::Tabularx, "lX");
+        table.use_booktabs(true).rows[0].background("gray!10");
+        let mut doc = Document::new(DocumentClass::Article);
+
+        for _ in 0..10 {
+            doc.add_table(&table);
+        }
+        let rendered = print(&doc).unwrap();
+
+        assert_eq!(rendered.matches(r"\usepackage{tabularx}").count(), 1);
+        assert_eq!(rendered.matches(r"\usepackage{booktabs}").count(), 1);
+        assert_eq!(rendered.matches(r"\usepackage[table]{xcolor}").count(), 1);
+    }
+
+    #[test]
+    fn add_the_table_option_to_an_existing_xcolor() {
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.row_colors(1, "gray!10", "white");
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble.use_package("xcolor");
+
+        table.prepare_document(&mut doc);
+        table.prepare_document(&mut doc);
+        let rendered = print(&doc).unwrap();
+
+        assert!(
+            rendered.contains(r"\usepackage[table]{xcolor}"),
+            "{}",
+            rendered
+        );
+        assert_eq!(rendered.matches("{xcolor}").count(), 1);
+    }
+
+    #[test]
+    fn environment_options_come_first() {
+        let should_be = r"\begin{xltabular}[c]{\textwidth}{lX}
+\end{xltabular}";
+        let mut table = Table::xltabular("lX");
+
+        table.env_options("c");
+
+        assert_eq!(table.to_string(), should_be);
+        assert!(Table::longtable("l")
+            .env_options("r")
+            .to_string()
+            .starts_with(r"\begin{longtable}[r]{l}"));
+    }
+
+    #[test]
+    fn standalone_document_for_a_table() {
+        let mut table = table_with_rows(TableKind::Tabularx, "lX");
+        table.use_booktabs(true);
+
+        let rendered = print(&table.into_standalone_document()).unwrap();
+
+        assert!(rendered.starts_with(r"\documentclass{article}"));
+        assert!(rendered.contains(r"\usepackage{tabularx}"));
+        assert!(rendered.contains(r"\usepackage{booktabs}"));
+        assert!(rendered.contains(r"\begin{document}"));
+        assert!(rendered.contains(r"\begin{tabularx}{\textwidth}{lX}"));
+        assert!(rendered.contains(r"\end{tabularx}"));
+    }
+
+    #[test]
+    fn identical_tables_have_the_same_hash() {
+        let mut table = table_with_rows(TableKind::Tabularx, "lX");
+        table.row_stretch(1.5).set_column_weight(1, 0.5).unwrap();
+        let mut tables = HashSet::new();
+
+        tables.insert(table.clone());
+        tables.insert(table);
+
+        assert_eq!(tables.len(), 1);
+    }
+
+    #[test]
+    fn pretty_tables_line_up_their_columns() {
+        let compact = r"\begin{tabular}{lr}
+Name & Age\\
+\midrule
+Alice & 42\\
+Bob & 7\\
+\hline
+\multicolumn{2}{c}{Everyone}\\
+\end{tabular}";
+        let pretty = r"\begin{tabular}{lr}
+Name  & Age\\
+\midrule
+Alice & 42 \\
+Bob   & 7  \\
+\hline
+\multicolumn{2}{c}{Everyone}\\
+\end{tabular}";
+        let mut table = Table::tabular("lr");
+        table
+            .push_row_values(vec!["Name", "Age"])
+            .unwrap()
+            .push_row_values(vec!["Alice", "42"])
+            .unwrap()
+            .push_row_values(vec!["Bob", "7"])
+            .unwrap();
+        table.rows[0].rule_after = Some(Rule::MidRule);
+        table.rows[2].rule_after = Some(Rule::HLine);
+        table
+            .push_row(Row::from_cells(vec![Cell::multicolumn(
+                2,
+                'c',
+                "Everyone".to_string(),
+            )]))
+            .unwrap();
+
+        assert_eq!(table.to_string(), compact);
+        assert_eq!(table.pretty(true).to_string(), pretty);
+    }
+
+    #[test]
+    fn caption_with_captionof() {
+        let should_be = r"\centering
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}
+\captionof{table}{Not a float}\label{tab:minipage}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table
+            .caption("Not a float")
+            .label("tab:minipage")
+            .placement("h")
+            .centered(true)
+            .use_captionof(true);
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        assert!(uses_package(&doc, "caption"));
+    }
+
+    #[test]
+    fn captionof_above_the_table() {
+        let should_be = r"\captionof{table}{Above}
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table
+            .caption("Above")
+            .caption_position(CaptionPosition::Above)
+            .use_captionof(true);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn trimmed_cmidrules_under_rows() {
+        let should_be = r"\begin{tabular}{lcr}
+a & b & c\\
+\cmidrule(lr){2-3}
+\end{tabular}";
+        let mut table = Table::tabular("lcr");
+        let mut row: Row = vec!["a", "b", "c"].into_iter().collect();
+        row.rule_after = Some(Rule::CMidRule(2, 3, Trim::Both));
+
+        table.push_row(row).unwrap();
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn rules_must_fit_in_the_table() {
+        let mut table = Table::tabular("lcr");
+        let mut row: Row = vec!["a", "b", "c"].into_iter().collect();
+        row.rule_after = Some(Rule::CMidRule(2, 4, Trim::Left));
+
+        let got = table.push_row(row.clone()).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::RuleOutOfRange {
+                start: 2,
+                end: 4,
+                column_count: 3,
+            }
+        );
+        for &range in &[(0, 1), (3, 2)] {
+            row.rule_after = Some(Rule::CLine(range.0, range.1));
+            table.push_row_unchecked(row.clone());
+            assert!(table.validate().is_err());
+            table.clear_rows();
+        }
+    }
+
+    #[test]
+    fn hyperlinks_need_hyperref() {
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        let mut doc = Document::new(DocumentClass::Article);
+        table.prepare_document(&mut doc);
+        assert!(!uses_package(&doc, "hyperref"));
+
+        table.rows[0].cells[1] = Cell::href("https://example.com".to_string(), "b".to_string());
+        table.prepare_document(&mut doc);
+
+        assert!(uses_package(&doc, "hyperref"));
+        assert!(table
+            .to_string()
+            .contains(r"a & \href{https://example.com}{b}\\"));
+    }
+
+    #[test]
+    fn pad_short_rows() {
+        let mut table = Table::tabular("lcr");
+
+        table
+            .push_row_padded(vec!["a"].into_iter().collect(), "-")
+            .unwrap()
+            .push_row_padded(vec!["b", "c", "d"].into_iter().collect(), "-")
+            .unwrap();
+
+        let values: Vec<&str> = table.rows[0].iter().map(|c| c.value.as_str()).collect();
+        assert_eq!(values, vec!["a", "-", "-"]);
+        assert_eq!(table.rows[1].cells[2].value, "d");
+    }
+
+    #[test]
+    fn padding_doesnt_allow_long_rows() {
+        let mut table = Table::tabular("lc");
+
+        let got = table
+            .push_row_padded(vec!["a", "b", "c"].into_iter().collect(), "")
+            .unwrap_err();
+
+        assert_eq!(got, TableError::WrongNumberOfColumns(3, 2));
+        assert_eq!(table.row_count(), 0);
+    }
+
+    #[test]
+    fn split_header_and_body_rows() {
+        let mut table = Table::tabular("l");
+        for value in &["h1", "a", "h2", "b"] {
+            table.push_row_values(vec![*value]).unwrap();
+        }
+        table.rows[0].is_header = true;
+        table.rows[2].is_header = true;
+
+        let headers: Vec<&str> = table
+            .header_rows()
+            .map(|row| row.cells[0].value.as_str())
+            .collect();
+        let body: Vec<&str> = table
+            .body_rows()
+            .map(|row| row.cells[0].value.as_str())
+            .collect();
+
+        assert_eq!(headers, vec!["h1", "h2"]);
+        assert_eq!(body, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn leave_off_the_last_row_terminator() {
+        let should_be = r"\begin{tabular}{lc}
+a & b\\
+c & d
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+
+        assert!(table.to_string().contains(r"c & d\\"));
+        table.trailing_row_terminator(false);
+
+        assert_eq!(table.to_string(), should_be);
+    }
+
+    #[test]
+    fn last_row_terminator_is_kept_before_rules() {
+        let mut table = table_with_rows(TableKind::LongTable, "lc");
+        table.trailing_row_terminator(false).use_booktabs(true);
+
+        assert!(table.to_string().contains("c & d\\\\\n\\bottomrule\n"));
+
+        table.use_booktabs(false).rows[1].rule_after = Some(Rule::HLine);
+        assert!(table.to_string().contains("c & d\\\\\n\\hline\n"));
+
+        table.rows[1].rule_after = None;
+        assert!(table.to_string().ends_with("c & d\n\\end{longtable}"));
+    }
+
+    #[test]
+    fn table_from_records() {
+        let people = [("Alice", 42), ("Bob", 7)];
+
+        let table = Table::from_records(
+            TableKind::Tabular,
+            "textwidth",
+            "lr",
+            people
+                .iter()
+                .map(|&(name, age)| vec![name.to_string(), age.to_string()]),
+        )
+        .unwrap();
+
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.rows[1].to_string(), r"Bob & 7\\");
+    }
+
+    #[test]
+    fn records_stop_at_the_first_bad_row() {
+        let mut visited = 0;
+        let records = (0..5).map(|i| {
+            visited += 1;
+            if i == 1 {
+                vec!["short".to_string()]
+            } else {
+                vec![i.to_string(), i.to_string()]
+            }
+        });
+
+        let got = Table::from_records(TableKind::Tabular, "textwidth", "ll", records).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::WrongNumberOfColumnsInRow {
+                row_index: 1,
+                provided: 1,
+                required: 2,
+            }
+        );
+        assert_eq!(visited, 2);
+    }
+
+    #[test]
+    fn colored_text_needs_xcolor() {
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rows[1].cells[0] = Cell::colored_text("c".to_string(), "red");
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert!(uses_package(&doc, "xcolor"));
+        assert!(table.to_string().contains(r"\textcolor{red}{c} & d\\"));
+    }
+
+    #[test]
+    fn rotate_by_90_degrees() {
+        let should_be = r"\begin{sidewaystable}
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}
+\caption{Wide}
+\end{sidewaystable}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.caption("Wide").rotate(90);
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        assert!(uses_package(&doc, "rotating"));
+        assert!(!uses_package(&doc, "graphicx"));
+    }
+
+    #[test]
+    fn rotate_a_bare_tabular_by_90_degrees() {
+        let should_be = r"\rotatebox{90}{
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}
+}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rotate(90);
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        assert!(uses_package(&doc, "graphicx"));
+        assert!(!uses_package(&doc, "rotating"));
+    }
+
+    #[test]
+    fn rotate_by_other_angles() {
+        let should_be = r"\rotatebox{270}{
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}
+}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.rotate(-90);
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        assert!(uses_package(&doc, "graphicx"));
+        assert_eq!(table.rotate(720).rotation(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Row 1 has 3 columns but the table requires 2")]
+    fn rendering_a_corrupted_table_panics() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.rows[1].push_cell("extra");
+
+        let _ = table.to_string();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Row 1 has 3 columns but the table requires 2")]
+    fn rendering_the_body_of_a_corrupted_table_panics() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.rows[1].push_cell("extra");
+
+        let _ = table.render_body();
+    }
+
+    #[test]
+    fn writing_a_corrupted_table_is_an_error() {
+        let mut table = table_with_rows(TableKind::Tabular, "ll");
+        table.rows[1].push_cell("extra");
+        let mut buffer = Vec::new();
+
+        match table.write_to(&mut buffer) {
+            Err(Error::Table(e)) => assert_eq!(
+                e,
+                TableError::WrongNumberOfColumnsInRow {
+                    row_index: 1,
+                    provided: 3,
+                    required: 2,
+                }
+            ),
+            other => panic!("Expected a table error, got {:?}", other),
+        }
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn alternating_row_colors() {
+        let should_be = r"\rowcolors{2}{gray!10}{white}
+\begin{tabular}{lc}
+a & b\\
+c & d\\
+\end{tabular}";
+        let mut table = table_with_rows(TableKind::Tabular, "lc");
+        table.row_colors(2, "gray!10", "white");
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert_eq!(table.to_string(), should_be);
+        let xcolor = PreambleElement::UsePackage {
+            package: "xcolor".to_string(),
+            argument: Some("table".to_string()),
+        };
+        assert!(doc.preamble.iter().any(|element| *element == xcolor));
+    }
+
+    #[test]
+    fn symbols_pull_in_their_packages() {
+        let mut table = Table::tabular("ll");
+        table
+            .push_row(Row::from_cells(vec![
+                Cell::symbol(Symbol::Check),
+                Cell::symbol(Symbol::Cross),
+            ]))
+            .unwrap();
+        let mut doc = Document::new(DocumentClass::Article);
+
+        table.prepare_document(&mut doc);
+
+        assert!(uses_package(&doc, "amssymb"));
+    }
+
+    #[test]
+    fn add_a_vertical_rule_between_columns() {
+        let should_be = r"\begin{tabular}{l>{\bfseries}c|p{2cm}r|}
+\end{tabular}";
+        let mut table = Table::tabular(r"l>{\bfseries}cp{2cm}r");
+
+        table
+            .add_vertical_rule(2)
+            .unwrap()
+            .add_vertical_rule(4)
+            .unwrap();
+
+        assert_eq!(table.to_string(), should_be);
+        assert_eq!(table.column_count(), 4);
+        assert_eq!(table.column_types(), r"l>{\bfseries}cp{2cm}r");
+        assert_eq!(
+            table.add_vertical_rule(5).unwrap_err(),
+            TableError::ColumnIndexOutOfBounds {
+                index: 5,
+                column_count: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn add_a_vertical_rule_before_a_decorated_column() {
+        let should_be = r"\begin{tabular}{l|>{\bfseries}c}
+\end{tabular}";
+        let mut table = Table::tabular(r"l>{\bfseries}c");
+
+        table.add_vertical_rule(1).unwrap();
+
+        assert_eq!(table.to_string(), should_be);
+    }
+}
@@ -0,0 +1,331 @@
+use super::{columns, Row, Table};
+
+/// Options for `Table::to_plain_text_with()`.
+///
+/// The defaults give the same output as `Table::to_plain_text()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextRenderOptions {
+    /// The number of spaces on either side of each value.
+    pub padding: usize,
+    /// Should the border be drawn around the outside of the table?
+    pub outer_border: bool,
+    /// The character drawn between two columns.
+    pub column_separator: char,
+}
+
+impl Default for TextRenderOptions {
+    fn default() -> Self {
+        TextRenderOptions {
+            padding: 1,
+            outer_border: true,
+            column_separator: '|',
+        }
+    }
+}
+
+impl Table {
+    /// Render the table as plain text with ASCII-art borders, e.g. for
+    /// printing to a terminal.
+    ///
+    /// Columns are padded using each cell's display width, so wide (e.g.
+    /// CJK) and combining characters still line up in a monospaced font.
+    /// `r` and `c` columns are right-aligned and centred, and every other
+    /// column is left-aligned. Cells which span several columns only show
+    /// their value in the first column.
+    ///
+    /// > **Note:** The display width is only an approximation of the Unicode
+    /// > East Asian Width rules. The symbols and dingbats in U+2600-U+27BF
+    /// > (e.g. `✅`) are counted as one column, even though terminals often
+    /// > draw the emoji among them two columns wide, so those may not line
+    /// > up.
+    pub fn to_plain_text(&self) -> String {
+        self.to_plain_text_with(&TextRenderOptions::default())
+    }
+
+    /// Render the table as plain text, like `to_plain_text()`, using custom
+    /// padding and borders.
+    ///
+    /// Without the outer border, only the rule under the header is drawn and
+    /// trailing whitespace is trimmed from each line.
+    pub fn to_plain_text_with(&self, options: &TextRenderOptions) -> String {
+        let letters = columns::column_letters(&self.column_types);
+        let rows: Vec<Vec<&str>> = self.rows.iter().map(column_values).collect();
+
+        let mut widths = vec![0; letters.len()];
+        for row in &rows {
+            for (i, value) in row.iter().enumerate() {
+                if i < widths.len() {
+                    widths[i] = widths[i].max(display_width(value));
+                }
+            }
+        }
+
+        let padding = " ".repeat(options.padding);
+        let separator = line(
+            widths
+                .iter()
+                .map(|width| "-".repeat(width + 2 * options.padding)),
+            '+',
+            options.outer_border,
+        );
+
+        let last_header = self.rows.iter().rposition(|row| row.is_header);
+        let mut rendered = String::new();
+        if options.outer_border {
+            rendered.push_str(&separator);
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            let values = widths.iter().enumerate().map(|(column, width)| {
+                let value = row.get(column).copied().unwrap_or("");
+                let extra = width - display_width(value);
+                let (before, after) = match letters[column] {
+                    'r' => (extra, 0),
+                    'c' => (extra / 2, extra - extra / 2),
+                    _ => (0, extra),
+                };
+
+                format!(
+                    "{}{}{}{}{}",
+                    padding,
+                    " ".repeat(before),
+                    value,
+                    " ".repeat(after),
+                    padding
+                )
+            });
+            rendered.push_str(&line(
+                values,
+                options.column_separator,
+                options.outer_border,
+            ));
+
+            if Some(i) == last_header && i + 1 < rows.len() {
+                rendered.push_str(&separator);
+            }
+        }
+
+        if options.outer_border {
+            rendered.push_str(&separator);
+        }
+        rendered
+    }
+}
+
+/// Join the pieces of a line together, optionally drawing the separator at
+/// either end too.
+fn line<I: Iterator<Item = String>>(pieces: I, separator: char, outer_border: bool) -> String {
+    let separator = separator.to_string();
+    let joined = pieces.collect::<Vec<_>>().join(&separator);
+
+    if outer_border {
+        format!("{}{}{}\n", separator, joined, separator)
+    } else {
+        format!("{}\n", joined.trim_end())
+    }
+}
+
+/// The text in each of the row's columns, with cells which span several
+/// columns followed by empty values.
+fn column_values(row: &Row) -> Vec<&str> {
+    let mut values = Vec::new();
+
+    for cell in row.iter() {
+        values.push(cell.value.as_str());
+        values.extend(vec![""; cell.column_span().saturating_sub(1)]);
+    }
+
+    values
+}
+
+/// How many columns of a monospaced terminal the text takes up.
+///
+/// This is an approximation of the Unicode East Asian Width rules, treating
+/// combining marks as zero-width and the common wide and fullwidth blocks
+/// (CJK, Hangul, fullwidth forms and the emoji blocks from U+1F300) as two
+/// columns wide. Everything else, including the emoji in the older symbol
+/// blocks, is one column wide.
+fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tables::{Cell, Symbol};
+    use TableKind;
+
+    #[test]
+    fn render_plain_text_table() {
+        let should_be = "+-------+-----+
+| Name  | Age |
++-------+-----+
+| Alice |  42 |
+| Bob   |   7 |
++-------+-----+
+";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+        table
+            .push_row_values(vec!["Name", "Age"])
+            .unwrap()
+            .push_row_values(vec!["Alice", "42"])
+            .unwrap()
+            .push_row_values(vec!["Bob", "7"])
+            .unwrap();
+        table.rows[0].is_header = true;
+
+        assert_eq!(table.to_plain_text(), should_be);
+    }
+
+    #[test]
+    fn pad_wide_characters() {
+        let should_be = "+--------+---+
+| 東京   | x |
+| Berlin | y |
++--------+---+
+";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+        table
+            .push_row_values(vec!["東京", "x"])
+            .unwrap()
+            .push_row_values(vec!["Berlin", "y"])
+            .unwrap();
+
+        assert_eq!(table.to_plain_text(), should_be);
+    }
+
+    #[test]
+    fn pad_combining_characters() {
+        // "Zoe\u{308}" is "Zoë" written with a combining diaeresis
+        let should_be = "+------+
+| Zoe\u{308}  |
+| Zoë  |
+| Anna |
++------+
+";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "l");
+        table
+            .push_row_values(vec!["Zoe\u{308}"])
+            .unwrap()
+            .push_row_values(vec!["Zoë"])
+            .unwrap()
+            .push_row_values(vec!["Anna"])
+            .unwrap();
+
+        assert_eq!(table.to_plain_text(), should_be);
+        assert_eq!(display_width("Zoe\u{308}"), 3);
+    }
+
+    fn people() -> Table {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lr");
+        table
+            .push_row_values(vec!["Name", "Age"])
+            .unwrap()
+            .push_row_values(vec!["Alice", "42"])
+            .unwrap();
+        table.rows[0].is_header = true;
+        table
+    }
+
+    #[test]
+    fn default_options_match_plain_text() {
+        let table = people();
+
+        let got = table.to_plain_text_with(&TextRenderOptions::default());
+
+        assert_eq!(got, table.to_plain_text());
+    }
+
+    #[test]
+    fn plain_text_with_extra_padding() {
+        let should_be = "+---------+-------+
+#  Name   #  Age  #
++---------+-------+
+#  Alice  #   42  #
++---------+-------+
+";
+        let options = TextRenderOptions {
+            padding: 2,
+            column_separator: '#',
+            ..Default::default()
+        };
+
+        assert_eq!(people().to_plain_text_with(&options), should_be);
+    }
+
+    #[test]
+    fn plain_text_without_outer_border() {
+        let should_be = " Name  | Age
+-------+-----
+ Alice |  42
+";
+        let options = TextRenderOptions {
+            outer_border: false,
+            ..Default::default()
+        };
+
+        assert_eq!(people().to_plain_text_with(&options), should_be);
+    }
+
+    #[test]
+    fn plain_text_without_padding_or_border() {
+        let should_be = "Name |Age
+-----+---
+Alice| 42
+";
+        let options = TextRenderOptions {
+            padding: 0,
+            outer_border: false,
+            ..Default::default()
+        };
+
+        assert_eq!(people().to_plain_text_with(&options), should_be);
+    }
+
+    #[test]
+    fn plain_text_shows_the_source_of_latex_cells() {
+        let should_be = "+------+---+-----+
+| a--b | ✓ | x^2 |
++------+---+-----+
+";
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lll");
+        table
+            .push_row(Row::from_cells(vec![
+                Cell::code("a--b".to_string()),
+                Cell::symbol(Symbol::Check),
+                Cell::math("x^2".to_string()),
+            ]))
+            .unwrap();
+
+        assert_eq!(table.to_plain_text(), should_be);
+    }
+}
@@ -0,0 +1,517 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::FromIterator;
+use std::mem;
+use std::slice::Iter;
+use std::vec::Drain;
+
+use super::{Cell, TableError};
+
+/// A single row in a `Table`.
+///
+/// When rendered, each cell is separated by ` & ` and the row is terminated
+/// with `\\` directly after the last cell (e.g. `a & b\\`), without a space
+/// in between.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Row {
+    /// The cells in this row.
+    pub cells: Vec<Cell>,
+    /// Is this row part of the table's header?
+    pub is_header: bool,
+    /// Is this row the header shown on a long table's first page?
+    ///
+    /// Unless the table has other header rows for the later pages, this row
+    /// is repeated on them too.
+    pub is_first_header: bool,
+    /// A horizontal rule to draw underneath this row.
+    pub rule_after: Option<Rule>,
+    /// The row's background colour.
+    pub color: Option<String>,
+    /// Extra vertical space to add underneath this row (using `booktabs`'
+    /// `\addlinespace`), where an empty string uses the default amount.
+    pub space_after: Option<String>,
+}
+
+impl Row {
+    /// Create an empty row.
+    pub fn new() -> Row {
+        Default::default()
+    }
+
+    /// Create a row from a list of cells.
+    pub fn from_cells(cells: Vec<Cell>) -> Row {
+        Row {
+            cells,
+            ..Default::default()
+        }
+    }
+
+    /// Create a body row from a list of cells.
+    ///
+    /// This is the same as `from_cells()`, for symmetry with `header()`.
+    pub fn body(cells: Vec<Cell>) -> Row {
+        Row::from_cells(cells)
+    }
+
+    /// Create a header row from a list of cells.
+    pub fn header(cells: Vec<Cell>) -> Row {
+        Row {
+            is_header: true,
+            ..Row::from_cells(cells)
+        }
+    }
+
+    /// Create a header row which is also the header on a long table's first
+    /// page.
+    pub fn first_header(cells: Vec<Cell>) -> Row {
+        Row {
+            is_first_header: true,
+            ..Row::header(cells)
+        }
+    }
+
+    /// Add a cell to the end of the row.
+    pub fn push_cell<S: AsRef<str>>(&mut self, value: S) -> &mut Self {
+        self.cells.push(Cell {
+            value: value.as_ref().to_string(),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Add a cell to the end of the row.
+    ///
+    /// This accepts anything which can be converted into a `Cell` using
+    /// `into()`, so plain strings can be used as well as fully customised
+    /// cells.
+    pub fn push<C: Into<Cell>>(&mut self, cell: C) -> &mut Self {
+        self.cells.push(cell.into());
+        self
+    }
+
+    /// Add a cell to the end of the row, making sure its value doesn't
+    /// contain any newlines.
+    pub fn try_push_cell<S: AsRef<str>>(&mut self, value: S) -> Result<&mut Self, TableError> {
+        let cell = Cell::new(value.as_ref().to_string())?;
+        self.cells.push(cell);
+        Ok(self)
+    }
+
+    /// Set the row's background colour (using `\rowcolor`).
+    ///
+    /// This requires the `xcolor` package to be loaded with the `table`
+    /// option, which is done for you by `Table::prepare_document()`.
+    pub fn background(&mut self, color: &str) -> &mut Self {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    /// Add some vertical space underneath this row (e.g. `1ex`) to separate
+    /// it from the next group of rows, or `""` to use the default amount.
+    ///
+    /// This requires the `booktabs` package, which is added for you by
+    /// `Table::prepare_document()`.
+    pub fn space_after(&mut self, dimension: &str) -> &mut Self {
+        self.space_after = Some(dimension.to_string());
+        self
+    }
+
+    /// Make the text in every cell of this row bold, as is often done for
+    /// header rows.
+    pub fn make_header_bold(&mut self) -> &mut Self {
+        for cell in &mut self.cells {
+            cell.style.bold = true;
+        }
+        self
+    }
+
+    /// The number of table columns this row takes up, taking cells which span
+    /// several columns into account.
+    pub fn column_count(&self) -> usize {
+        self.cells.iter().map(Cell::column_span).sum()
+    }
+
+    /// The number of cells in this row.
+    ///
+    /// Cells which span several columns only count once, use
+    /// `column_count()` to get the number of columns this row takes up.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Does this row have no cells?
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Get the cell at `index`, if there is one.
+    pub fn cell(&self, index: usize) -> Option<&Cell> {
+        self.cells.get(index)
+    }
+
+    /// Get a mutable reference to the cell at `index`, if there is one.
+    pub fn cell_mut(&mut self, index: usize) -> Option<&mut Cell> {
+        self.cells.get_mut(index)
+    }
+
+    /// Iterate over the cells in this row.
+    pub fn iter(&self) -> Iter<'_, Cell> {
+        self.cells.iter()
+    }
+
+    /// Move every cell out of this row, leaving it empty once the iterator is
+    /// dropped.
+    pub fn drain_cells(&mut self) -> Drain<'_, Cell> {
+        self.cells.drain(..)
+    }
+
+    /// Take every cell out of this row without cloning them, leaving it
+    /// empty.
+    pub fn take_cells(&mut self) -> Vec<Cell> {
+        mem::take(&mut self.cells)
+    }
+}
+
+impl Row {
+    /// Write the row with each cell padded to the width of its column, so
+    /// the columns line up with the other rows.
+    ///
+    /// Cells which span several columns aren't padded. The `\\` terminator
+    /// is only written when `terminated` is set.
+    pub(crate) fn fmt_padded<W: fmt::Write>(
+        &self,
+        f: &mut W,
+        widths: &[usize],
+        terminated: bool,
+    ) -> fmt::Result {
+        if let Some(ref color) = self.color {
+            write!(f, r"\rowcolor{{{}}} ", color)?;
+        }
+
+        let mut column = 0;
+        for (i, cell) in self.cells.iter().enumerate() {
+            if i > 0 {
+                write!(f, " & ")?;
+            }
+
+            match widths.get(column) {
+                Some(&width) if cell.column_span() == 1 => {
+                    write!(f, "{:<width$}", cell.to_string(), width = width)?
+                }
+                _ => write!(f, "{}", cell)?,
+            }
+            column += cell.column_span();
+        }
+
+        if terminated {
+            write!(f, r"\\")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Row {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_padded(f, &[], true)
+    }
+}
+
+/// A horizontal rule drawn between two rows.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Rule {
+    /// A rule across the entire table (`\hline`).
+    HLine,
+    /// A rule across the (1-based, inclusive) range of columns (`\cline`).
+    CLine(usize, usize),
+    /// A `booktabs` rule across the entire table (`\midrule`).
+    MidRule,
+    /// A `booktabs` rule across the (1-based, inclusive) range of columns
+    /// (`\cmidrule`), optionally trimmed at either end.
+    CMidRule(usize, usize, Trim),
+}
+
+/// Which ends of a `\cmidrule` to trim, so rules over neighbouring column
+/// ranges don't touch.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Trim {
+    /// Don't trim the rule.
+    #[default]
+    None,
+    /// Trim the left end (`(l)`).
+    Left,
+    /// Trim the right end (`(r)`).
+    Right,
+    /// Trim both ends (`(lr)`).
+    Both,
+}
+
+impl Display for Trim {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Trim::None => Ok(()),
+            Trim::Left => write!(f, "(l)"),
+            Trim::Right => write!(f, "(r)"),
+            Trim::Both => write!(f, "(lr)"),
+        }
+    }
+}
+
+impl Rule {
+    /// Does this rule come from the `booktabs` package?
+    pub fn requires_booktabs(&self) -> bool {
+        match *self {
+            Rule::MidRule | Rule::CMidRule(..) => true,
+            Rule::HLine | Rule::CLine(..) => false,
+        }
+    }
+
+    /// The (1-based, inclusive) range of columns a partial rule goes across.
+    pub fn column_range(&self) -> Option<(usize, usize)> {
+        match *self {
+            Rule::CLine(start, end) | Rule::CMidRule(start, end, _) => Some((start, end)),
+            Rule::HLine | Rule::MidRule => None,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Rule::HLine => write!(f, r"\hline"),
+            Rule::CLine(start, end) => write!(f, r"\cline{{{}-{}}}", start, end),
+            Rule::MidRule => write!(f, r"\midrule"),
+            Rule::CMidRule(start, end, trim) => {
+                write!(f, r"\cmidrule{}{{{}-{}}}", trim, start, end)
+            }
+        }
+    }
+}
+
+impl FromIterator<String> for Row {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut row = Row::new();
+        for value in iter {
+            row.push_cell(value);
+        }
+        row
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Row {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut row = Row::new();
+        for value in iter {
+            row.push_cell(value);
+        }
+        row
+    }
+}
+
+impl FromIterator<Cell> for Row {
+    fn from_iter<I: IntoIterator<Item = Cell>>(iter: I) -> Self {
+        Row::from_cells(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_row_from_strs() {
+        let row: Row = vec!["a", "b", "c"].into_iter().collect();
+
+        let values: Vec<&str> = row.iter().map(|c| c.value.as_str()).collect();
+        assert_eq!(values, vec!["a", "b", "c"]);
+        assert!(!row.is_header);
+        assert!(!row.is_first_header);
+    }
+
+    #[test]
+    fn collect_row_from_strings() {
+        let row: Row = (1..4).map(|i| i.to_string()).collect();
+
+        assert_eq!(row.cells.len(), 3);
+        assert_eq!(row.cells[2].value, "3");
+    }
+
+    #[test]
+    fn try_push_a_valid_cell() {
+        let mut row = Row::new();
+
+        row.try_push_cell("a").unwrap().try_push_cell("b").unwrap();
+
+        assert_eq!(row.cells.len(), 2);
+    }
+
+    #[test]
+    fn try_push_a_cell_with_a_newline() {
+        let mut row = Row::new();
+
+        let got = row.try_push_cell("line 1\nline 2").unwrap_err();
+
+        assert_eq!(got, TableError::CellContainsNewline);
+        assert!(row.cells.is_empty());
+    }
+
+    #[test]
+    fn render_row_with_background() {
+        let mut row: Row = vec!["a", "b"].into_iter().collect();
+
+        row.background("gray!20");
+
+        assert_eq!(row.to_string(), r"\rowcolor{gray!20} a & b\\");
+    }
+
+    #[test]
+    fn make_header_row_bold() {
+        let mut row: Row = vec!["Name", "Age"].into_iter().collect();
+        row.is_header = true;
+
+        row.make_header_bold();
+
+        assert_eq!(row.to_string(), r"\textbf{Name} & \textbf{Age}\\");
+    }
+
+    #[test]
+    fn render_rules() {
+        assert_eq!(Rule::HLine.to_string(), r"\hline");
+        assert_eq!(Rule::CLine(2, 3).to_string(), r"\cline{2-3}");
+        assert_eq!(Rule::MidRule.to_string(), r"\midrule");
+        assert_eq!(
+            Rule::CMidRule(1, 2, Trim::None).to_string(),
+            r"\cmidrule{1-2}"
+        );
+    }
+
+    #[test]
+    fn row_from_cells() {
+        let cells = vec![Cell::multicolumn(2, 'c', "wide".to_string())];
+
+        let row = Row::from_cells(cells.clone());
+
+        assert_eq!(row.cells, cells);
+        assert_eq!(row.column_count(), 2);
+    }
+
+    #[test]
+    fn row_terminator_has_no_leading_space() {
+        let row: Row = vec!["a", "b"].into_iter().collect();
+
+        assert_eq!(row.to_string(), r"a & b\\");
+    }
+
+    #[test]
+    fn render_empty_row() {
+        assert_eq!(Row::new().to_string(), r"\\");
+    }
+
+    #[test]
+    fn render_very_wide_row() {
+        let values: Vec<String> = (0..1000).map(|i| i.to_string()).collect();
+        let row: Row = values.iter().map(|v| v.as_str()).collect();
+
+        let should_be = format!(r"{}\\", values.join(" & "));
+
+        assert_eq!(row.to_string(), should_be);
+    }
+
+    #[test]
+    fn push_anything_which_converts_into_a_cell() {
+        let mut row = Row::new();
+
+        row.push("a")
+            .push("b".to_string())
+            .push(Cell::multicolumn(2, 'c', "c".to_string()));
+
+        assert_eq!(row.to_string(), r"a & b & \multicolumn{2}{c}{c}\\");
+    }
+
+    #[test]
+    fn collect_cells_into_a_row() {
+        let row: Row = vec!["a", "b"].into_iter().map(Cell::from).collect();
+
+        assert_eq!(row.cells.len(), 2);
+        assert_eq!(row.to_string(), r"a & b\\");
+    }
+
+    #[test]
+    fn row_length() {
+        let mut row = Row::new();
+        assert!(row.is_empty());
+        assert_eq!(row.len(), 0);
+
+        row.push(Cell::multicolumn(2, 'c', "wide".to_string()));
+
+        assert!(!row.is_empty());
+        assert_eq!(row.len(), 1);
+        assert_eq!(row.column_count(), 2);
+    }
+
+    #[test]
+    fn access_cells() {
+        let mut row: Row = vec!["a", "b"].into_iter().collect();
+
+        assert_eq!(row.cell(1).unwrap().value, "b");
+        assert_eq!(row.cell(2), None);
+
+        row.cell_mut(0).unwrap().value = "c".to_string();
+        assert_eq!(row.cells[0].value, "c");
+        assert_eq!(row.cell_mut(2), None);
+    }
+
+    #[test]
+    fn header_constructors_set_the_flags() {
+        let cells = vec![Cell::from("a"), Cell::from("b")];
+
+        let body = Row::body(cells.clone());
+        let header = Row::header(cells.clone());
+        let first_header = Row::first_header(cells.clone());
+
+        assert_eq!((body.is_header, body.is_first_header), (false, false));
+        assert_eq!((header.is_header, header.is_first_header), (true, false));
+        assert_eq!(
+            (first_header.is_header, first_header.is_first_header),
+            (true, true)
+        );
+        assert_eq!(first_header.cells, cells);
+    }
+
+    #[test]
+    fn render_trimmed_cmidrules() {
+        let rules = [
+            Rule::CMidRule(2, 3, Trim::Left),
+            Rule::CMidRule(2, 3, Trim::Right),
+            Rule::CMidRule(2, 3, Trim::Both),
+        ];
+
+        let got: Vec<String> = rules.iter().map(Rule::to_string).collect();
+
+        assert_eq!(
+            got,
+            vec![
+                r"\cmidrule(l){2-3}",
+                r"\cmidrule(r){2-3}",
+                r"\cmidrule(lr){2-3}"
+            ]
+        );
+    }
+
+    #[test]
+    fn move_cells_out_of_a_row() {
+        let mut row: Row = vec!["a", "b"].into_iter().collect();
+        row.is_header = true;
+        let original = row.cells.clone();
+
+        let taken = row.take_cells();
+
+        assert_eq!(taken, original);
+        assert!(row.is_empty());
+        assert!(row.is_header);
+
+        row.cells = taken;
+        let drained: Vec<Cell> = row.drain_cells().rev().collect();
+        assert_eq!(drained[0].value, "b");
+        assert!(row.is_empty());
+    }
+}
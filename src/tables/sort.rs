@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+
+use super::{Row, Table, TableError};
+
+impl Table {
+    /// Sort the table's body rows by the value in one of their columns.
+    ///
+    /// When `numeric` is set, values are compared as numbers, with any values
+    /// which aren't numbers coming after all the numbers (in string order).
+    /// Otherwise values are compared as strings. Header rows stay where they
+    /// are, and rows with equal values keep their order.
+    ///
+    /// Moving rows around would break up cells which span several rows, so
+    /// body rows can't contain `\multirow` or `\multicolumn` cells.
+    pub fn sort_by_column(&mut self, column_index: usize, numeric: bool) -> Result<(), TableError> {
+        if column_index >= self.column_count {
+            return Err(TableError::ColumnIndexOutOfBounds {
+                index: column_index,
+                column_count: self.column_count,
+            });
+        }
+
+        for (row_index, row) in self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !row.is_header)
+        {
+            if let Some(column_index) = row
+                .iter()
+                .position(|cell| cell.multicolumn.is_some() || cell.multirow.is_some())
+            {
+                return Err(TableError::SpanningCell {
+                    row_index,
+                    column_index,
+                });
+            }
+        }
+
+        let positions: Vec<usize> = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !row.is_header)
+            .map(|(i, _)| i)
+            .collect();
+        let mut body: Vec<Row> = positions.iter().map(|&i| self.rows[i].clone()).collect();
+
+        body.sort_by(|a, b| {
+            let a = column_value(a, column_index);
+            let b = column_value(b, column_index);
+
+            if numeric {
+                compare_numbers(a, b)
+            } else {
+                a.cmp(b)
+            }
+        });
+
+        for (position, row) in positions.into_iter().zip(body) {
+            self.rows[position] = row;
+        }
+
+        Ok(())
+    }
+}
+
+/// The value of the cell which starts in this column, or `""` if there isn't
+/// one.
+fn column_value(row: &Row, column_index: usize) -> &str {
+    let mut column = 0;
+
+    for cell in row.iter() {
+        if column == column_index {
+            return &cell.value;
+        }
+        column += cell.column_span();
+    }
+
+    ""
+}
+
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tables::Cell;
+
+    fn table_with_values(values: &[&str]) -> Table {
+        let mut table = Table::tabular("ll");
+        table.push_row_values(vec!["Name", "Value"]).unwrap();
+        table.rows[0].is_header = true;
+
+        for (i, value) in values.iter().enumerate() {
+            table
+                .push_row_values(vec![i.to_string(), value.to_string()])
+                .unwrap();
+        }
+
+        table
+    }
+
+    fn column(table: &Table, index: usize) -> Vec<&str> {
+        table
+            .iter()
+            .map(|row| row.cells[index].value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn sort_rows_as_strings() {
+        let mut table = table_with_values(&["pear", "10", "apple", "9"]);
+
+        table.sort_by_column(1, false).unwrap();
+
+        assert_eq!(column(&table, 1), vec!["Value", "10", "9", "apple", "pear"]);
+        assert!(table.rows[0].is_header);
+    }
+
+    #[test]
+    fn sort_rows_as_numbers() {
+        let mut table = table_with_values(&["10", "n/a", "-2.5", "9", "1e3"]);
+
+        table.sort_by_column(1, true).unwrap();
+
+        assert_eq!(
+            column(&table, 1),
+            vec!["Value", "-2.5", "9", "10", "1e3", "n/a"]
+        );
+        assert_eq!(column(&table, 0), vec!["Name", "2", "3", "0", "4", "1"]);
+    }
+
+    #[test]
+    fn headers_keep_their_position() {
+        let mut table = table_with_values(&["c", "b", "a"]);
+        table.rows[2].is_header = true;
+
+        table.sort_by_column(1, false).unwrap();
+
+        assert_eq!(column(&table, 1), vec!["Value", "a", "b", "c"]);
+        assert!(table.rows[2].is_header);
+    }
+
+    #[test]
+    fn rows_with_spans_cant_be_sorted() {
+        let mut table = table_with_values(&["b", "a"]);
+        table.rows[1].cells[0] = Cell::multirow(2, "*", "both".to_string());
+        let before = table.clone();
+
+        let got = table.sort_by_column(1, false).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::SpanningCell {
+                row_index: 1,
+                column_index: 0,
+            }
+        );
+        assert_eq!(table, before);
+    }
+
+    #[test]
+    fn sort_by_a_missing_column() {
+        let mut table = table_with_values(&["b", "a"]);
+
+        let got = table.sort_by_column(2, false).unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::ColumnIndexOutOfBounds {
+                index: 2,
+                column_count: 2,
+            }
+        );
+    }
+}
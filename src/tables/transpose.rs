@@ -0,0 +1,273 @@
+use super::{columns, Row, Table, TableError};
+
+impl Table {
+    /// Create a new table with this table's rows and columns swapped, so the
+    /// first column becomes the first row and so on.
+    ///
+    /// Every column in the new table is an `l` column. When this table starts
+    /// with a header row, the new table's first row (this table's first
+    /// column) becomes its header. Any other table settings (e.g. the caption)
+    /// are kept, but row colours and rules are dropped.
+    ///
+    /// Cells which span several columns or rows can't be transposed, and
+    /// every row must have the same number of columns.
+    pub fn transpose(&self) -> Result<Table, TableError> {
+        self.check_rearrangeable()?;
+
+        let has_header = self.rows.first().is_some_and(|row| row.is_header);
+        let mut transposed = self.clone();
+        transposed.column_types = "l".repeat(self.rows.len());
+        transposed.column_count = self.rows.len();
+        transposed.column_weights.clear();
+        transposed.vertical_rules.clear();
+        transposed.rows = (0..self.column_count)
+            .map(|i| {
+                let mut row =
+                    Row::from_cells(self.rows.iter().map(|r| r.cells[i].clone()).collect());
+                row.is_header = has_header && i == 0;
+                row.is_first_header = row.is_header;
+                row
+            })
+            .collect();
+
+        Ok(transposed)
+    }
+
+    /// Split a wide table into several narrower tables with at most
+    /// `max_columns` columns each, e.g. so they can be stacked on the page.
+    ///
+    /// The first `key_columns` columns (e.g. a name column) are repeated at
+    /// the start of every table, followed by as many of the remaining columns
+    /// as fit. Each table's column specification is taken from the matching
+    /// parts of this table's, and header rows and the table's settings are
+    /// kept. Only the first table gets the caption and label, so the label
+    /// isn't defined several times. Column weights, vertical rules added with
+    /// `add_vertical_rule()` and rules which only go across some of the
+    /// columns are dropped.
+    ///
+    /// There must be room for at least one column besides the key columns,
+    /// otherwise this gives a `TableError::TooManyKeyColumns`. A table with
+    /// nothing but key columns is returned as it is.
+    ///
+    /// Like `transpose()`, cells which span several columns or rows can't be
+    /// split and every row must have the same number of columns.
+    pub fn split_columns(
+        &self,
+        max_columns: usize,
+        key_columns: usize,
+    ) -> Result<Vec<Table>, TableError> {
+        self.check_rearrangeable()?;
+
+        if key_columns >= max_columns {
+            return Err(TableError::TooManyKeyColumns {
+                key_columns,
+                max_columns,
+            });
+        }
+        if key_columns >= self.column_count {
+            return Ok(vec![self.clone()]);
+        }
+
+        let (pieces, rest) = columns::split_spec(&self.column_types);
+        let per_table = max_columns - key_columns;
+
+        let mut template = self.clone();
+        template.rows.clear();
+        template.column_weights.clear();
+        template.vertical_rules.clear();
+
+        let others: Vec<usize> = (key_columns..self.column_count).collect();
+        let tables = others
+            .chunks(per_table)
+            .enumerate()
+            .map(|(n, chunk)| {
+                let indices: Vec<usize> = (0..key_columns).chain(chunk.iter().cloned()).collect();
+                // fall back to plain columns if the spec couldn't be parsed
+                let mut spec: String = indices
+                    .iter()
+                    .map(|&i| pieces.get(i).map_or("l", |piece| piece.as_str()))
+                    .collect();
+                spec.push_str(&rest);
+
+                let mut table = template.clone();
+                table.column_types = spec;
+                if n > 0 {
+                    table.caption = None;
+                    table.label = None;
+                }
+                table.column_count = indices.len();
+                table.rows = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        let mut split = Row {
+                            cells: indices.iter().map(|&i| row.cells[i].clone()).collect(),
+                            ..row.clone()
+                        };
+                        if split
+                            .rule_after
+                            .as_ref()
+                            .and_then(|rule| rule.column_range())
+                            .is_some()
+                        {
+                            split.rule_after = None;
+                        }
+                        split
+                    })
+                    .collect();
+                table
+            })
+            .collect();
+
+        Ok(tables)
+    }
+
+    /// Make sure the table's columns can be moved around, without any cells
+    /// spanning several columns or rows.
+    fn check_rearrangeable(&self) -> Result<(), TableError> {
+        for (row_index, row) in self.rows.iter().enumerate() {
+            if let Some(column_index) = row
+                .iter()
+                .position(|cell| cell.multicolumn.is_some() || cell.multirow.is_some())
+            {
+                return Err(TableError::SpanningCell {
+                    row_index,
+                    column_index,
+                });
+            }
+
+            if row.cells.len() != self.column_count {
+                return Err(TableError::WrongNumberOfColumnsInRow {
+                    row_index,
+                    provided: row.cells.len(),
+                    required: self.column_count,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tables::{Cell, TableKind};
+
+    #[test]
+    fn transpose_a_2x3_table() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "lcr");
+        let mut header: Row = vec!["Name", "Age", "City"].into_iter().collect();
+        header.is_header = true;
+        table
+            .push_row(header)
+            .unwrap()
+            .push_row_values(vec!["Alice", "42", "Perth"])
+            .unwrap();
+
+        let got = table.transpose().unwrap();
+
+        assert_eq!(got.column_types, "ll");
+        assert_eq!(got.column_count(), 2);
+        assert_eq!(got.rows.len(), 3);
+        assert!(got.rows[0].is_header);
+        assert!(!got.rows[1].is_header);
+        assert_eq!(got.rows[0].to_string(), r"Name & Alice\\");
+        assert_eq!(got.rows[1].to_string(), r"Age & 42\\");
+        assert_eq!(got.rows[2].to_string(), r"City & Perth\\");
+    }
+
+    #[test]
+    fn ragged_tables_cant_be_transposed() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+        table.push_row_values(vec!["a", "b"]).unwrap();
+        table.rows.push(vec!["c"].into_iter().collect());
+
+        let got = table.transpose().unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::WrongNumberOfColumnsInRow {
+                row_index: 1,
+                provided: 1,
+                required: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn spanning_cells_cant_be_transposed() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+        let row = Row::from_cells(vec![Cell::multicolumn(2, 'c', "wide".to_string())]);
+        table.push_row(row).unwrap();
+
+        let got = table.transpose().unwrap_err();
+
+        assert_eq!(
+            got,
+            TableError::SpanningCell {
+                row_index: 0,
+                column_index: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn split_a_wide_table() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "|l|ccc@{}rrrr|");
+        let mut header: Row = vec!["Name", "a", "b", "c", "d", "e", "f", "g"]
+            .into_iter()
+            .collect();
+        header.is_header = true;
+        table
+            .push_row(header)
+            .unwrap()
+            .push_row_values(vec!["x", "1", "2", "3", "4", "5", "6", "7"])
+            .unwrap()
+            .caption("Wide")
+            .label("tab:wide");
+
+        let got = table.split_columns(4, 1).unwrap();
+
+        let specs: Vec<&str> = got.iter().map(|t| t.column_types.as_str()).collect();
+        assert_eq!(specs, vec!["|l|ccc|", "|l@{}rrr|", "|lr|"]);
+        assert_eq!(got[0].rows[0].to_string(), r"Name & a & b & c\\");
+        assert_eq!(got[1].rows[1].to_string(), r"x & 4 & 5 & 6\\");
+        assert_eq!(got[2].rows[1].to_string(), r"x & 7\\");
+        for chunk in &got {
+            assert!(chunk.rows[0].is_header);
+            assert!(!chunk.rows[1].is_header);
+            assert_eq!(chunk.validate(), Ok(()));
+        }
+        assert!(got[0].to_string().contains(r"\caption{Wide}"));
+        assert!(got[0].to_string().contains(r"\label{tab:wide}"));
+        assert!(!got[1].to_string().contains(r"\caption"));
+        assert!(!got[2].to_string().contains(r"\label"));
+    }
+
+    #[test]
+    fn split_a_table_with_only_key_columns() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "ll");
+        table.push_row_values(vec!["a", "b"]).unwrap();
+
+        let got = table.split_columns(4, 2).unwrap();
+
+        assert_eq!(got, vec![table]);
+    }
+
+    #[test]
+    fn split_tables_need_room_for_more_than_the_key_columns() {
+        let mut table = Table::new(TableKind::Tabular, "textwidth", "llll");
+        table.push_row_values(vec!["a", "b", "c", "d"]).unwrap();
+
+        for &(max_columns, key_columns) in &[(2, 2), (1, 3), (0, 0)] {
+            assert_eq!(
+                table.split_columns(max_columns, key_columns).unwrap_err(),
+                TableError::TooManyKeyColumns {
+                    key_columns,
+                    max_columns,
+                }
+            );
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 mod printer;
 
-pub use self::printer::{print, Printer};
+pub use self::printer::print;
 
 use document::{Document, DocumentClass, Element, Preamble};
 use equations::{Align, Equation};
@@ -36,8 +36,8 @@ pub trait Visitor {
     /// `visit_*()` methods.
     ///
     /// > **Note:** You probably don't want to implement this one yourself. If
-    /// you forget to recursively visit each and every variant of `Element`
-    /// you may end up accidentally ignoring half your document!
+    /// > you forget to recursively visit each and every variant of `Element`
+    /// > you may end up accidentally ignoring half your document!
     fn visit_element(&mut self, elem: &Element) -> Result<(), Error> {
         match *elem {
             Element::Para(ref p) => self.visit_paragraph(p)?,